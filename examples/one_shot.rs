@@ -0,0 +1,16 @@
+use braiinspool::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    // Construct client without keeping idle connections alive (i.e. for serverless functions)
+    let client = BraiinsPoolClient::builder("apikey").one_shot().build()?;
+
+    // Get pool stats
+    let pool_stats: PoolStats = client.pool_stats().await?;
+    println!("{:#?}", pool_stats);
+
+    // Drop client (closes the remaining connections, if any)
+    drop(client);
+
+    Ok(())
+}
@@ -0,0 +1,104 @@
+//! Pool API trait
+//!
+//! Depend on [`PoolApi`] instead of [`BraiinsPoolClient`] to inject a fake in tests.
+
+use std::future::Future;
+
+use crate::client::BraiinsPoolClient;
+use crate::error::Error;
+use crate::model::{DailyRewards, PoolStats, UserProfile, Workers};
+
+/// Braiins Pool API
+pub trait PoolApi {
+    /// Get pool stats
+    fn pool_stats(&self) -> impl Future<Output = Result<PoolStats, Error>> + Send;
+
+    /// Get user profile
+    fn user_profile(&self) -> impl Future<Output = Result<UserProfile, Error>> + Send;
+
+    /// Get daily rewards
+    fn daily_rewards(&self) -> impl Future<Output = Result<DailyRewards, Error>> + Send;
+
+    /// Get workers
+    fn workers(&self) -> impl Future<Output = Result<Workers, Error>> + Send;
+}
+
+impl PoolApi for BraiinsPoolClient {
+    #[inline]
+    fn pool_stats(&self) -> impl Future<Output = Result<PoolStats, Error>> + Send {
+        BraiinsPoolClient::pool_stats(self)
+    }
+
+    #[inline]
+    fn user_profile(&self) -> impl Future<Output = Result<UserProfile, Error>> + Send {
+        BraiinsPoolClient::user_profile(self)
+    }
+
+    #[inline]
+    fn daily_rewards(&self) -> impl Future<Output = Result<DailyRewards, Error>> + Send {
+        BraiinsPoolClient::daily_rewards(self)
+    }
+
+    #[inline]
+    fn workers(&self) -> impl Future<Output = Result<Workers, Error>> + Send {
+        BraiinsPoolClient::workers(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    struct FakeApi;
+
+    impl PoolApi for FakeApi {
+        async fn pool_stats(&self) -> Result<PoolStats, Error> {
+            Err(Error::NotFound)
+        }
+
+        async fn user_profile(&self) -> Result<UserProfile, Error> {
+            Err(Error::NotFound)
+        }
+
+        async fn daily_rewards(&self) -> Result<DailyRewards, Error> {
+            Ok(DailyRewards {
+                daily_rewards: Vec::new(),
+            })
+        }
+
+        async fn workers(&self) -> Result<Workers, Error> {
+            Ok(Workers {
+                workers: HashMap::new(),
+            })
+        }
+    }
+
+    async fn count_workers<A>(api: &A) -> Result<usize, Error>
+    where
+        A: PoolApi,
+    {
+        Ok(api.workers().await?.workers.len())
+    }
+
+    #[tokio::test]
+    async fn test_fake_api() {
+        let api = FakeApi;
+        assert_eq!(count_workers(&api).await.unwrap(), 0);
+        assert!(api.daily_rewards().await.unwrap().daily_rewards.is_empty());
+        assert!(matches!(api.pool_stats().await, Err(Error::NotFound)));
+
+        // Futures must be `Send`, to be spawned
+        tokio::spawn(async move { count_workers(&api).await })
+            .await
+            .unwrap()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_client_implements_pool_api() {
+        fn assert_pool_api<A: PoolApi>() {}
+        assert_pool_api::<BraiinsPoolClient>();
+    }
+}
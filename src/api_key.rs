@@ -0,0 +1,85 @@
+//! API key
+
+use std::fmt;
+
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// API key
+///
+/// Redacted from the [`Debug`] output.
+/// With the `zeroize` feature, its memory is zeroed on drop.
+#[derive(Clone)]
+pub struct ApiKey(String);
+
+impl ApiKey {
+    /// Construct a new API key
+    #[inline]
+    pub fn new<T>(api_key: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self(api_key.into())
+    }
+
+    /// Get API key as string
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for ApiKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ApiKey(<redacted>)")
+    }
+}
+
+impl From<String> for ApiKey {
+    #[inline]
+    fn from(api_key: String) -> Self {
+        Self(api_key)
+    }
+}
+
+impl From<&str> for ApiKey {
+    #[inline]
+    fn from(api_key: &str) -> Self {
+        Self(api_key.to_string())
+    }
+}
+
+impl From<&String> for ApiKey {
+    #[inline]
+    fn from(api_key: &String) -> Self {
+        Self(api_key.clone())
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for ApiKey {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_debug_redacts_api_key() {
+        let api_key = ApiKey::new("s3cr3t-t0k3n");
+        let debug: String = format!("{api_key:?}");
+        assert!(!debug.contains("s3cr3t-t0k3n"));
+        assert_eq!(debug, "ApiKey(<redacted>)");
+        assert_eq!(api_key.as_str(), "s3cr3t-t0k3n");
+    }
+
+    #[test]
+    fn test_from() {
+        assert_eq!(ApiKey::from("apikey").as_str(), "apikey");
+        assert_eq!(ApiKey::from(String::from("apikey")).as_str(), "apikey");
+        assert_eq!(ApiKey::from(&String::from("apikey")).as_str(), "apikey");
+    }
+}
@@ -1,76 +1,488 @@
 //! Braiins Pool client builder
 
+#[cfg(all(
+    any(feature = "rustls", feature = "nativetls"),
+    not(target_arch = "wasm32")
+))]
+use std::any::Any;
+use std::collections::HashMap;
+use std::net::IpAddr;
 #[cfg(feature = "socks")]
 use std::net::SocketAddr;
+use std::sync::Arc;
 use std::time::Duration;
+use std::{env, fmt};
 
 #[cfg(feature = "socks")]
 use reqwest::Proxy;
-use reqwest::header::{HeaderMap, HeaderValue};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+#[cfg(all(
+    any(feature = "rustls", feature = "nativetls"),
+    not(target_arch = "wasm32")
+))]
+use reqwest::tls::Certificate;
 use reqwest::{Client, ClientBuilder};
+#[cfg(all(feature = "socks", not(target_arch = "wasm32")))]
+use url::ParseError;
+use url::Url;
 
+use crate::api_key::ApiKey;
 use crate::client::BraiinsPoolClient;
 use crate::error::Error;
+use crate::rate_limit::RateLimiter;
+
+/// Applies a preconfigured TLS backend to the reqwest builder
+#[cfg(all(
+    any(feature = "rustls", feature = "nativetls"),
+    not(target_arch = "wasm32")
+))]
+type PreconfiguredTls = Arc<dyn Fn(ClientBuilder) -> ClientBuilder + Send + Sync>;
 
 const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+/// Upper bound, well above the length of current tokens
+const MAX_API_KEY_LEN: usize = 256;
+const DEFAULT_USER_AGENT: &str = concat!("braiinspool-rs/", env!("CARGO_PKG_VERSION"));
+
+/// Environment variable read by [`BraiinsPoolClientBuilder::from_env`]
+pub const API_KEY_ENV_VAR: &str = "BRAIINS_POOL_TOKEN";
 
 /// Braiins Pool client builder
-#[derive(Debug, Clone)]
+///
+/// The API key is redacted from the [`Debug`] output (see [`ApiKey`]).
+#[derive(Clone)]
 pub struct BraiinsPoolClientBuilder {
-    /// API key
-    pub api_key: String,
-    /// Timeout
+    /// API key (`None` for [anonymous](BraiinsPoolClientBuilder::anonymous) clients)
+    pub api_key: Option<ApiKey>,
+    /// Base URL (default: `https://pool.braiins.com`)
+    pub base_url: Option<String>,
+    /// Pre-parsed base URL (see [`BraiinsPoolClientBuilder::base_url_parsed`])
+    pub base_url_parsed: Option<Url>,
+    /// Fallback base URL, used when the base URL is unreachable
+    pub fallback_base_url: Option<String>,
+    /// Timeout (overall request deadline)
     pub timeout: Duration,
+    /// Connect timeout (default: none, apart from the overall timeout)
+    pub connect_timeout: Option<Duration>,
+    /// User agent
+    pub user_agent: String,
+    /// Additional default headers
+    pub headers: HashMap<String, String>,
+    /// Idle connections timeout (default: reqwest's one)
+    pub pool_idle_timeout: Option<Duration>,
+    /// Max idle connections per host (default: reqwest's one)
+    pub pool_max_idle_per_host: Option<usize>,
+    /// Local address to bind outbound connections to
+    pub local_address: Option<IpAddr>,
+    /// Max JSON nesting depth of the responses (default: no limit, apart from the `serde_json` one)
+    pub max_json_depth: Option<usize>,
+    /// Rate limit: `(requests, per)` (default: no limit)
+    pub rate_limit: Option<(u32, Duration)>,
     /// Socks5 proxy
     #[cfg(feature = "socks")]
     pub proxy: Option<SocketAddr>,
+    /// Resolve DNS through the proxy (default: `true`)
+    #[cfg(feature = "socks")]
+    pub proxy_remote_dns: bool,
+    /// Proxy URL (see [`BraiinsPoolClientBuilder::proxy_str`])
+    #[cfg(feature = "socks")]
+    pub proxy_str: Option<String>,
+    /// Additional trusted root certificates
+    #[cfg(all(
+        any(feature = "rustls", feature = "nativetls"),
+        not(target_arch = "wasm32")
+    ))]
+    pub root_certificates: Vec<Certificate>,
+    #[cfg(all(
+        any(feature = "rustls", feature = "nativetls"),
+        not(target_arch = "wasm32")
+    ))]
+    preconfigured_tls: Option<PreconfiguredTls>,
+}
+
+impl fmt::Debug for BraiinsPoolClientBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("BraiinsPoolClientBuilder");
+        debug
+            .field("api_key", &self.api_key)
+            .field("base_url", &self.base_url)
+            .field("base_url_parsed", &self.base_url_parsed)
+            .field("fallback_base_url", &self.fallback_base_url)
+            .field("timeout", &self.timeout)
+            .field("connect_timeout", &self.connect_timeout)
+            .field("user_agent", &self.user_agent)
+            .field("headers", &self.headers)
+            .field("pool_idle_timeout", &self.pool_idle_timeout)
+            .field("pool_max_idle_per_host", &self.pool_max_idle_per_host)
+            .field("local_address", &self.local_address)
+            .field("max_json_depth", &self.max_json_depth)
+            .field("rate_limit", &self.rate_limit);
+        #[cfg(feature = "socks")]
+        debug
+            .field("proxy", &self.proxy)
+            .field("proxy_remote_dns", &self.proxy_remote_dns)
+            .field("proxy_str", &self.proxy_str);
+        #[cfg(all(
+            any(feature = "rustls", feature = "nativetls"),
+            not(target_arch = "wasm32")
+        ))]
+        debug
+            .field("root_certificates", &self.root_certificates)
+            .field("preconfigured_tls", &self.preconfigured_tls.is_some());
+        debug.finish()
+    }
 }
 
 impl BraiinsPoolClientBuilder {
     /// Construct a new builder
     pub fn new<T>(api_key: T) -> Self
     where
-        T: Into<String>,
+        T: Into<ApiKey>,
     {
         Self {
-            api_key: api_key.into(),
+            api_key: Some(api_key.into()),
+            ..Self::anonymous()
+        }
+    }
+
+    /// Construct a new builder without API key
+    ///
+    /// The auth token header is omitted: public endpoints (i.e. [`BraiinsPoolClient::pool_stats`]) work,
+    /// while the authenticated ones fail with [`Error::Unauthorized`].
+    pub fn anonymous() -> Self {
+        Self {
+            api_key: None,
+            base_url: None,
+            base_url_parsed: None,
+            fallback_base_url: None,
             timeout: DEFAULT_TIMEOUT,
+            connect_timeout: None,
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            headers: HashMap::new(),
+            pool_idle_timeout: None,
+            pool_max_idle_per_host: None,
+            local_address: None,
+            max_json_depth: None,
+            rate_limit: None,
             #[cfg(feature = "socks")]
             proxy: None,
+            #[cfg(feature = "socks")]
+            proxy_remote_dns: true,
+            #[cfg(feature = "socks")]
+            proxy_str: None,
+            #[cfg(all(
+                any(feature = "rustls", feature = "nativetls"),
+                not(target_arch = "wasm32")
+            ))]
+            root_certificates: Vec::new(),
+            #[cfg(all(
+                any(feature = "rustls", feature = "nativetls"),
+                not(target_arch = "wasm32")
+            ))]
+            preconfigured_tls: None,
         }
     }
 
+    /// Construct a new builder, reading the API key from the [`API_KEY_ENV_VAR`] (`BRAIINS_POOL_TOKEN`) environment variable
+    ///
+    /// Returns [`Error::Env`] if the variable is unset (or not unicode) and [`Error::InvalidApiKey`] if it's empty.
+    pub fn from_env() -> Result<Self, Error> {
+        let api_key: ApiKey = ApiKey::from(env::var(API_KEY_ENV_VAR)?);
+
+        if api_key.as_str().is_empty() {
+            return Err(Error::InvalidApiKey);
+        }
+
+        Ok(Self::new(api_key))
+    }
+
+    /// Set base URL (default: `https://pool.braiins.com`)
+    #[inline]
+    pub fn base_url<T>(mut self, url: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.base_url = Some(url.into());
+        self.base_url_parsed = None;
+        self
+    }
+
+    /// Set pre-parsed base URL (default: `https://pool.braiins.com`)
+    ///
+    /// Alternative to [`BraiinsPoolClientBuilder::base_url`], skipping the parsing: the latest call wins.
+    #[inline]
+    pub fn base_url_parsed(mut self, url: Url) -> Self {
+        self.base_url_parsed = Some(url);
+        self.base_url = None;
+        self
+    }
+
+    /// Set fallback base URL
+    ///
+    /// Requests are retried against it when the base URL is unreachable (connection errors only).
+    #[inline]
+    pub fn fallback_base_url<T>(mut self, url: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.fallback_base_url = Some(url.into());
+        self
+    }
+
     /// Set timeout (default: 60 sec)
+    ///
+    /// Overall deadline of a request, from connection to the end of the body.
     pub fn timeout(mut self, timeout: Duration) -> Self {
         self.timeout = timeout;
         self
     }
 
+    /// Set connect timeout
+    ///
+    /// Applies to the connection phase only, to fail fast on unreachable hosts:
+    /// the overall [`timeout`](BraiinsPoolClientBuilder::timeout) still applies.
+    #[inline]
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Set user agent (default: `braiinspool-rs/<version>`)
+    #[inline]
+    pub fn user_agent<T>(mut self, user_agent: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    /// Set timeout for idle sockets being kept-alive (default: reqwest's one)
+    #[inline]
+    pub fn pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.pool_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the maximum idle connections per host (default: reqwest's one)
+    #[inline]
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    /// Don't keep idle connections alive, for one-shot usage (i.e. serverless functions)
+    ///
+    /// Sockets are closed as soon as each request completes,
+    /// instead of lingering until the last clone of the client is dropped.
+    /// Shorthand for `pool_max_idle_per_host(0)` and `pool_idle_timeout(Duration::ZERO)`.
+    #[inline]
+    pub fn one_shot(self) -> Self {
+        self.pool_max_idle_per_host(0)
+            .pool_idle_timeout(Duration::ZERO)
+    }
+
+    /// Add a default header, sent with every request
+    ///
+    /// The auth token header can't be overridden.
+    #[inline]
+    pub fn header<K, V>(mut self, name: K, value: V) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.headers.insert(name.into(), value.into());
+        self
+    }
+
+    /// Bind outbound connections to a local address
+    #[inline]
+    pub fn local_address(mut self, addr: IpAddr) -> Self {
+        self.local_address = Some(addr);
+        self
+    }
+
+    /// Set the max JSON nesting depth (objects and arrays) of the responses
+    ///
+    /// Deeper responses are rejected with [`Error::MaxJsonDepthExceeded`], before deserialization.
+    #[inline]
+    pub fn max_json_depth(mut self, max_depth: usize) -> Self {
+        self.max_json_depth = Some(max_depth);
+        self
+    }
+
+    /// Limit the outgoing requests to `requests` every `per` (token bucket)
+    ///
+    /// Allows bursts of up to `requests`; when the bucket is empty, calls wait for a token instead of failing.
+    /// The limit is shared between the clones of the client.
+    /// A zero `requests` or `per` disables the limit.
+    #[inline]
+    pub fn rate_limit(mut self, requests: u32, per: Duration) -> Self {
+        self.rate_limit = Some((requests, per));
+        self
+    }
+
     /// Set proxy
+    ///
+    /// The port must be nonzero, or the build fails with [`Error::Url`].
     #[inline]
     #[cfg(feature = "socks")]
     pub fn proxy(mut self, proxy: SocketAddr) -> Self {
         self.proxy = Some(proxy);
+        self.proxy_str = None;
+        self
+    }
+
+    /// Set proxy from a full URL (i.e. `socks5h://host:port`)
+    ///
+    /// Alternative to [`BraiinsPoolClientBuilder::proxy`]: the latest call wins.
+    /// The URL is parsed at build time and must have a nonzero port, or the build fails with [`Error::Url`].
+    /// [`BraiinsPoolClientBuilder::proxy_remote_dns`] doesn't apply: the scheme is used as is.
+    #[inline]
+    #[cfg(feature = "socks")]
+    pub fn proxy_str<T>(mut self, proxy: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.proxy_str = Some(proxy.into());
+        self.proxy = None;
+        self
+    }
+
+    /// Set whether DNS is resolved through the proxy (`socks5h`) or locally (`socks5`) (default: `true`)
+    #[inline]
+    #[cfg(feature = "socks")]
+    pub fn proxy_remote_dns(mut self, remote_dns: bool) -> Self {
+        self.proxy_remote_dns = remote_dns;
+        self
+    }
+
+    /// Add a trusted root certificate (i.e. a private CA fronting the pool)
+    #[inline]
+    #[cfg(all(
+        any(feature = "rustls", feature = "nativetls"),
+        not(target_arch = "wasm32")
+    ))]
+    pub fn add_root_certificate(mut self, certificate: Certificate) -> Self {
+        self.root_certificates.push(certificate);
+        self
+    }
+
+    /// Use a preconfigured TLS backend
+    ///
+    /// Passed to [`reqwest::ClientBuilder::use_preconfigured_tls`]:
+    /// must be a `rustls::ClientConfig` (`rustls` feature) or a `native_tls::TlsConnector` (`nativetls` feature),
+    /// otherwise [`BraiinsPoolClientBuilder::build`] fails.
+    #[cfg(all(
+        any(feature = "rustls", feature = "nativetls"),
+        not(target_arch = "wasm32")
+    ))]
+    pub fn use_preconfigured_tls<T>(mut self, tls: T) -> Self
+    where
+        T: Any + Clone + Send + Sync,
+    {
+        self.preconfigured_tls = Some(Arc::new(move |builder: ClientBuilder| {
+            builder.use_preconfigured_tls(tls.clone())
+        }));
         self
     }
 
     /// Build client
     pub fn build(self) -> Result<BraiinsPoolClient, Error> {
-        let mut auth_value = HeaderValue::from_str(&self.api_key)?;
-        auth_value.set_sensitive(true);
+        let mut headers: HeaderMap = HeaderMap::with_capacity(1 + self.headers.len());
 
-        let mut headers: HeaderMap = HeaderMap::with_capacity(1);
-        headers.insert("Pool-Auth-Token", auth_value);
+        // Add custom headers
+        for (name, value) in self.headers.iter() {
+            let name: HeaderName = HeaderName::from_bytes(name.as_bytes())?;
+            let value: HeaderValue = HeaderValue::from_str(value)?;
+            headers.insert(name, value);
+        }
+
+        // Add auth token
+        if let Some(api_key) = &self.api_key {
+            // Check API key
+            if !is_valid_api_key(api_key.as_str()) {
+                return Err(Error::InvalidApiKey);
+            }
+
+            let mut auth_value = HeaderValue::from_str(api_key.as_str())?;
+            auth_value.set_sensitive(true);
+
+            headers.insert("Pool-Auth-Token", auth_value);
+        }
 
         let mut builder: ClientBuilder = Client::builder().default_headers(headers);
 
+        // Set TLS backend
+        // `nativetls` takes precedence, since `rustls` is enabled by default
+        #[cfg(all(feature = "nativetls", not(target_arch = "wasm32")))]
+        {
+            builder = builder.use_native_tls();
+        }
+        #[cfg(all(
+            feature = "rustls",
+            not(feature = "nativetls"),
+            not(target_arch = "wasm32")
+        ))]
+        {
+            builder = builder.use_rustls_tls();
+        }
+
+        // Set root certificates and preconfigured TLS backend
+        #[cfg(all(
+            any(feature = "rustls", feature = "nativetls"),
+            not(target_arch = "wasm32")
+        ))]
+        {
+            for certificate in self.root_certificates.into_iter() {
+                builder = builder.add_root_certificate(certificate);
+            }
+
+            if let Some(preconfigured_tls) = self.preconfigured_tls {
+                builder = preconfigured_tls(builder);
+            }
+        }
+
+        // Set user agent
+        builder = builder.user_agent(self.user_agent);
+
+        // Enable response decompression (also sets `Accept-Encoding`)
+        #[cfg(all(feature = "gzip", not(target_arch = "wasm32")))]
+        {
+            builder = builder.gzip(true);
+        }
+        #[cfg(all(feature = "brotli", not(target_arch = "wasm32")))]
+        {
+            builder = builder.brotli(true);
+        }
+
         // Set timeout
         builder = builder.timeout(self.timeout);
 
+        // Set connect timeout
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+
+        // Set connection pool options
+        if let Some(timeout) = self.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(timeout);
+        }
+        if let Some(max) = self.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max);
+        }
+
+        // Set local address
+        if let Some(addr) = self.local_address {
+            builder = builder.local_address(addr);
+        }
+
         // Set proxy
         #[cfg(all(feature = "socks", not(target_arch = "wasm32")))]
-        if let Some(proxy) = self.proxy {
-            let proxy: String = format!("socks5h://{proxy}");
+        if let Some(proxy) = self.proxy_str {
+            let proxy: Url = parse_proxy_str(&proxy)?;
+            builder = builder.proxy(Proxy::all(proxy)?);
+        } else if let Some(proxy) = self.proxy {
+            let proxy: String = proxy_url(proxy, self.proxy_remote_dns)?;
             builder = builder.proxy(Proxy::all(proxy)?);
         }
 
@@ -78,6 +490,469 @@ impl BraiinsPoolClientBuilder {
         let client: Client = builder.build()?;
 
         // Construct client
-        Ok(BraiinsPoolClient::from_client(client))
+        let mut client: BraiinsPoolClient = BraiinsPoolClient::from_client(client);
+
+        // Set base URL
+        if let Some(base_url) = self.base_url_parsed {
+            client.url = base_url;
+        } else if let Some(base_url) = self.base_url {
+            client.url = Url::parse(&base_url)?;
+        }
+
+        // Set max JSON depth
+        client.max_json_depth = self.max_json_depth;
+
+        // Set rate limiter
+        client.rate_limiter = self
+            .rate_limit
+            .and_then(|(requests, per)| RateLimiter::new(requests, per))
+            .map(Arc::new);
+
+        // Set fallback base URL
+        if let Some(fallback_base_url) = self.fallback_base_url {
+            client.fallback_url = Some(Url::parse(&fallback_base_url)?);
+        }
+
+        Ok(client)
+    }
+}
+
+/// Format the socks5 proxy URL, checking that the port is nonzero
+#[cfg(all(feature = "socks", not(target_arch = "wasm32")))]
+fn proxy_url(proxy: SocketAddr, remote_dns: bool) -> Result<String, Error> {
+    if proxy.port() == 0 {
+        return Err(Error::Url(ParseError::InvalidPort));
+    }
+
+    let scheme: &str = if remote_dns { "socks5h" } else { "socks5" };
+    Ok(format!("{scheme}://{proxy}"))
+}
+
+/// Parse a proxy URL, checking that the port is nonzero
+#[cfg(all(feature = "socks", not(target_arch = "wasm32")))]
+fn parse_proxy_str(proxy: &str) -> Result<Url, Error> {
+    let url: Url = Url::parse(proxy)?;
+
+    match url.port_or_known_default() {
+        Some(0) | None => Err(Error::Url(ParseError::InvalidPort)),
+        Some(..) => Ok(url),
+    }
+}
+
+/// Basic API key sanity check (non-empty, reasonable length, printable ASCII without spaces)
+///
+/// Lenient on purpose, to not reject future key formats.
+pub(crate) fn is_valid_api_key(api_key: &str) -> bool {
+    !api_key.is_empty()
+        && api_key.len() <= MAX_API_KEY_LEN
+        && api_key.bytes().all(|b| b.is_ascii_graphic())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use wiremock::matchers::{header, method};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::*;
+
+    #[test]
+    fn test_build_with_local_address() {
+        let builder =
+            BraiinsPoolClientBuilder::new("apikey").local_address(IpAddr::V4(Ipv4Addr::LOCALHOST));
+        assert_eq!(builder.local_address, Some(IpAddr::V4(Ipv4Addr::LOCALHOST)));
+        assert!(builder.build().is_ok());
+    }
+
+    #[test]
+    fn test_api_key_validation() {
+        assert!(BraiinsPoolClientBuilder::new("apikey").build().is_ok());
+        assert!(
+            BraiinsPoolClientBuilder::new("a1B2-c3_D4.e5+f6/g7=")
+                .build()
+                .is_ok()
+        );
+
+        for api_key in [
+            String::new(),
+            String::from("   "),
+            String::from("api key"),
+            String::from("apikey\n"),
+            String::from("apikéy"),
+            "a".repeat(MAX_API_KEY_LEN + 1),
+        ] {
+            let res = BraiinsPoolClientBuilder::new(api_key).build();
+            assert!(matches!(res, Err(Error::InvalidApiKey)));
+        }
+
+        let api_key = ApiKey::new("apikey");
+        assert!(BraiinsPoolClientBuilder::new(api_key).build().is_ok());
+    }
+
+    #[test]
+    #[cfg(all(feature = "socks", not(target_arch = "wasm32")))]
+    fn test_proxy_remote_dns() {
+        let proxy: SocketAddr = SocketAddr::from(([127, 0, 0, 1], 9050));
+
+        let builder = BraiinsPoolClientBuilder::new("apikey").proxy(proxy);
+        assert!(builder.proxy_remote_dns);
+        assert_eq!(
+            proxy_url(proxy, builder.proxy_remote_dns).unwrap(),
+            "socks5h://127.0.0.1:9050"
+        );
+
+        let builder = builder.proxy_remote_dns(false);
+        assert_eq!(
+            proxy_url(proxy, builder.proxy_remote_dns).unwrap(),
+            "socks5://127.0.0.1:9050"
+        );
+        assert!(builder.build().is_ok());
+    }
+
+    #[test]
+    #[cfg(all(feature = "socks", not(target_arch = "wasm32")))]
+    fn test_proxy_validation() {
+        // Socket address
+        let builder =
+            BraiinsPoolClientBuilder::new("apikey").proxy(SocketAddr::from(([127, 0, 0, 1], 0)));
+        assert!(matches!(
+            builder.build(),
+            Err(Error::Url(ParseError::InvalidPort))
+        ));
+
+        // String
+        let builder = BraiinsPoolClientBuilder::new("apikey").proxy_str("socks5h://127.0.0.1:9050");
+        assert_eq!(
+            builder.proxy_str.as_deref(),
+            Some("socks5h://127.0.0.1:9050")
+        );
+        assert!(builder.build().is_ok());
+
+        assert!(matches!(
+            BraiinsPoolClientBuilder::new("apikey")
+                .proxy_str("not a url")
+                .build(),
+            Err(Error::Url(..))
+        ));
+        assert!(matches!(
+            BraiinsPoolClientBuilder::new("apikey")
+                .proxy_str("socks5h://127.0.0.1")
+                .build(),
+            Err(Error::Url(ParseError::InvalidPort))
+        ));
+        assert!(matches!(
+            BraiinsPoolClientBuilder::new("apikey")
+                .proxy_str("socks5h://127.0.0.1:0")
+                .build(),
+            Err(Error::Url(ParseError::InvalidPort))
+        ));
+
+        // The latest call wins
+        let builder = BraiinsPoolClientBuilder::new("apikey")
+            .proxy_str("not a url")
+            .proxy(SocketAddr::from(([127, 0, 0, 1], 9050)));
+        assert!(builder.proxy_str.is_none());
+        assert!(builder.build().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_base_url_parsed() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(header("Pool-Auth-Token", "apikey"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let url: Url = Url::parse(&server.uri()).unwrap();
+        let client = BraiinsPoolClientBuilder::new("apikey")
+            .base_url("not a url")
+            .base_url_parsed(url.clone())
+            .build()
+            .unwrap();
+        assert_eq!(client.url, url);
+
+        let err = client.pool_stats().await.unwrap_err();
+        assert!(matches!(err, Error::NotFound));
+        server.verify().await;
+
+        // The latest call wins
+        let builder = BraiinsPoolClientBuilder::new("apikey")
+            .base_url_parsed(url)
+            .base_url("not a url");
+        assert!(builder.base_url_parsed.is_none());
+        assert!(matches!(builder.build(), Err(Error::Url(..))));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(4)
+            .mount(&server)
+            .await;
+
+        let client = BraiinsPoolClientBuilder::new("apikey")
+            .base_url(server.uri())
+            .rate_limit(2, Duration::from_millis(400))
+            .build()
+            .unwrap();
+
+        // 2 immediately, then one every 200 ms, shared between clones
+        let start = std::time::Instant::now();
+        let clone = client.clone();
+        let (a, b) = tokio::join!(client.pool_stats(), clone.pool_stats());
+        assert!(matches!(a, Err(Error::NotFound)));
+        assert!(matches!(b, Err(Error::NotFound)));
+        client.pool_stats().await.unwrap_err();
+        clone.pool_stats().await.unwrap_err();
+        assert!(start.elapsed() >= Duration::from_millis(400));
+
+        server.verify().await;
+
+        // Disabled
+        let client = BraiinsPoolClientBuilder::new("apikey")
+            .rate_limit(0, Duration::from_secs(1))
+            .build()
+            .unwrap();
+        assert!(client.rate_limiter.is_none());
+    }
+
+    #[test]
+    fn test_invalid_base_url() {
+        let res = BraiinsPoolClientBuilder::new("apikey")
+            .base_url("not a url")
+            .build();
+        assert!(matches!(res, Err(Error::Url(..))));
+    }
+
+    #[test]
+    fn test_build_with_pool_options() {
+        let builder = BraiinsPoolClientBuilder::new("apikey")
+            .pool_idle_timeout(Duration::from_secs(30))
+            .pool_max_idle_per_host(4);
+        assert_eq!(builder.pool_idle_timeout, Some(Duration::from_secs(30)));
+        assert_eq!(builder.pool_max_idle_per_host, Some(4));
+        assert!(builder.build().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_connect_timeout() {
+        let builder = BraiinsPoolClientBuilder::new("apikey")
+            .timeout(Duration::from_millis(200))
+            .connect_timeout(Duration::from_secs(5));
+        assert_eq!(builder.timeout, Duration::from_millis(200));
+        assert_eq!(builder.connect_timeout, Some(Duration::from_secs(5)));
+
+        // The overall timeout still applies to slow responses
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(2)))
+            .mount(&server)
+            .await;
+
+        let client = builder.base_url(server.uri()).build().unwrap();
+        match client.pool_stats().await.unwrap_err() {
+            Error::Reqwest(e) => assert!(e.is_timeout()),
+            e => panic!("unexpected error: {e}"),
+        }
+    }
+
+    #[test]
+    fn test_default_user_agent() {
+        let builder = BraiinsPoolClientBuilder::new("apikey");
+        assert_eq!(
+            builder.user_agent,
+            format!("braiinspool-rs/{}", env!("CARGO_PKG_VERSION"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_user_agent_header() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let client = BraiinsPoolClientBuilder::new("apikey")
+            .base_url(server.uri())
+            .user_agent("my-app/1.0")
+            .build()
+            .unwrap();
+
+        // The empty body fails to deserialize, only the request matters here
+        let _ = client.pool_stats().await;
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].headers.get("user-agent").unwrap(), "my-app/1.0");
+    }
+
+    #[test]
+    fn test_invalid_header() {
+        let res = BraiinsPoolClientBuilder::new("apikey")
+            .header("X-Api-Gateway-Key", "invalid\nvalue")
+            .build();
+        assert!(matches!(res, Err(Error::InvalidHeaderValue(..))));
+
+        let res = BraiinsPoolClientBuilder::new("apikey")
+            .header("invalid name", "value")
+            .build();
+        assert!(matches!(res, Err(Error::InvalidHeaderName(..))));
+    }
+
+    #[tokio::test]
+    async fn test_custom_headers() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(header("Pool-Auth-Token", "apikey"))
+            .and(header("X-Api-Gateway-Key", "gateway-key"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = BraiinsPoolClientBuilder::new("apikey")
+            .base_url(server.uri())
+            .header("X-Api-Gateway-Key", "gateway-key")
+            .header("Pool-Auth-Token", "override")
+            .build()
+            .unwrap();
+
+        // The empty body fails to deserialize, only the request matters here
+        let _ = client.pool_stats().await;
+
+        server.verify().await;
+    }
+
+    #[tokio::test]
+    async fn test_one_shot() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let builder = BraiinsPoolClientBuilder::new("apikey")
+            .base_url(server.uri())
+            .one_shot();
+        assert_eq!(builder.pool_max_idle_per_host, Some(0));
+        assert_eq!(builder.pool_idle_timeout, Some(Duration::ZERO));
+
+        let client = builder.build().unwrap();
+        client.pool_stats().await.unwrap_err();
+        client.pool_stats().await.unwrap_err();
+        drop(client);
+
+        server.verify().await;
+    }
+
+    #[tokio::test]
+    async fn test_anonymous() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(&server)
+            .await;
+
+        let builder = BraiinsPoolClientBuilder::anonymous().base_url(server.uri());
+        assert!(builder.api_key.is_none());
+
+        let client = builder.build().unwrap();
+        let err = client.user_profile().await.unwrap_err();
+        assert!(matches!(err, Error::Unauthorized));
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 1);
+        assert!(!requests[0].headers.contains_key("Pool-Auth-Token"));
+
+        let client = BraiinsPoolClient::new_anonymous().unwrap();
+        assert_eq!(client.url.as_str(), "https://pool.braiins.com/");
+    }
+
+    #[tokio::test]
+    #[cfg(all(any(feature = "gzip", feature = "brotli"), not(target_arch = "wasm32")))]
+    async fn test_accept_encoding() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let client = BraiinsPoolClientBuilder::new("apikey")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        client.pool_stats().await.unwrap_err();
+
+        let requests = server.received_requests().await.unwrap();
+        let accept_encoding: &str = requests[0]
+            .headers
+            .get("Accept-Encoding")
+            .unwrap()
+            .to_str()
+            .unwrap();
+        #[cfg(feature = "gzip")]
+        assert!(accept_encoding.contains("gzip"));
+        #[cfg(feature = "brotli")]
+        assert!(accept_encoding.contains("br"));
+    }
+
+    #[test]
+    fn test_debug_redacts_api_key() {
+        let builder = BraiinsPoolClientBuilder::new("s3cr3t-t0k3n").base_url("http://127.0.0.1");
+        let debug: String = format!("{builder:?}");
+        assert!(!debug.contains("s3cr3t-t0k3n"));
+        assert!(debug.contains("<redacted>"));
+        assert!(debug.contains("http://127.0.0.1"));
+
+        let client = builder.build().unwrap();
+        assert!(!format!("{client:?}").contains("s3cr3t-t0k3n"));
+    }
+
+    #[cfg(all(
+        any(feature = "rustls", feature = "nativetls"),
+        not(target_arch = "wasm32")
+    ))]
+    const CA_PEM: &[u8] = b"-----BEGIN CERTIFICATE-----
+MIIBkjCCATmgAwIBAgIUAoCNS6ZhLek8agnQUpC9pymuBtkwCgYIKoZIzj0EAwIw
+HjEcMBoGA1UEAwwTYnJhaWluc3Bvb2wgdGVzdCBDQTAgFw0yNjEwMTYwMDU2NDha
+GA8yMTI2MDkyMjAwNTY0OFowHjEcMBoGA1UEAwwTYnJhaWluc3Bvb2wgdGVzdCBD
+QTBZMBMGByqGSM49AgEGCCqGSM49AwEHA0IABMOkdNkJoW4KhN2pI9h5WpnKACkW
+UNmk61yPYkfym81rdNe/u3yd9w+eohAIsBUnIPor918IyjEpnfBD1N6hNCCjUzBR
+MB0GA1UdDgQWBBSfqzSGBUzUZ+QCMk4B0dYJGjgG9jAfBgNVHSMEGDAWgBSfqzSG
+BUzUZ+QCMk4B0dYJGjgG9jAPBgNVHRMBAf8EBTADAQH/MAoGCCqGSM49BAMCA0cA
+MEQCICv7zaym8zR+GqHq8wl1kEuKwQ7rKz/I9s8Uu8bjk3yOAiAWWqF3OWNdqBKd
+AKgyfKoKPsCq2R0CkkGz9UeYWGXWTA==
+-----END CERTIFICATE-----
+";
+
+    #[test]
+    #[cfg(all(
+        any(feature = "rustls", feature = "nativetls"),
+        not(target_arch = "wasm32")
+    ))]
+    fn test_add_root_certificate() {
+        let certificate = Certificate::from_pem(CA_PEM).unwrap();
+        let builder = BraiinsPoolClientBuilder::new("apikey").add_root_certificate(certificate);
+        assert_eq!(builder.root_certificates.len(), 1);
+        assert!(builder.build().is_ok());
+    }
+
+    #[test]
+    #[cfg(all(
+        any(feature = "rustls", feature = "nativetls"),
+        not(target_arch = "wasm32")
+    ))]
+    fn test_use_preconfigured_tls_unknown_backend() {
+        let res = BraiinsPoolClientBuilder::new("apikey")
+            .use_preconfigured_tls(())
+            .build();
+        assert!(matches!(res, Err(Error::Reqwest(..))));
     }
 }
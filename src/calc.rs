@@ -0,0 +1,73 @@
+//! Calculations
+
+use crate::model::{HashRate, HashRateUnit};
+
+/// Estimate the daily reward (BTC/day) for a hash rate under FPPS
+///
+/// `fpps_rate` is the [`PoolStats::fpps_rate`](crate::model::PoolStats::fpps_rate),
+/// expressed in **BTC per TH/s per day**, so:
+///
+/// `reward = hash_rate (TH/s) * fpps_rate`
+pub fn estimate_daily_reward(hash_rate: HashRate, fpps_rate: f64) -> f64 {
+    hash_rate.convert(HashRateUnit::TH).value() * fpps_rate
+}
+
+/// Fraction (`0.0`-`1.0`) of the pool hash rate contributed by the user
+///
+/// Both hash rates are compared as **hashes/sec**, so their units can differ.
+/// Returns `0.0` if the pool hash rate is zero.
+pub fn share_of_pool(user: HashRate, pool: HashRate) -> f64 {
+    let pool: f64 = pool.to_hashes();
+
+    if pool == 0.0 {
+        return 0.0;
+    }
+
+    user.to_hashes() / pool
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{PoolStats, UserProfile};
+    use crate::test_util;
+
+    #[test]
+    fn test_estimate_daily_reward() {
+        let mut profile: UserProfile = test_util::user_profile();
+        profile.hash_rate_24h = HashRate::new(HashRateUnit::GH, 100000.0);
+
+        // 100000 Gh/s = 100 Th/s, 100 * 0.00000241 = 0.000241 BTC/day
+        let reward: f64 = estimate_daily_reward(profile.hash_rate_24h, 0.00000241);
+        assert!((reward - 0.000241).abs() < 1e-12);
+
+        // 27978 Gh/s = 27.978 Th/s, 27.978 * 0.00000241 = 0.00006742698 BTC/day
+        let reward: f64 = estimate_daily_reward(profile.hash_rate_5m, 0.00000241);
+        assert!((reward - 0.00006742698).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_share_of_pool() {
+        let mut profile: UserProfile = test_util::user_profile();
+        profile.hash_rate_24h = HashRate::new(HashRateUnit::GH, 55170.0);
+        let mut stats: PoolStats = test_util::pool_stats();
+        stats.pool_24h_hash_rate = HashRate::new(HashRateUnit::GH, 5517000000.0);
+
+        // 55170 Gh/s / 5517000000 Gh/s = 0.00001
+        let share: f64 = share_of_pool(profile.hash_rate_24h, stats.pool_24h_hash_rate);
+        assert!((share - 0.00001).abs() < 1e-15);
+
+        // Units are converted
+        let share: f64 = share_of_pool(
+            profile.hash_rate_24h.convert(HashRateUnit::TH),
+            stats.pool_24h_hash_rate.convert(HashRateUnit::EH),
+        );
+        assert!((share - 0.00001).abs() < 1e-15);
+
+        stats.pool_24h_hash_rate = HashRate::new(HashRateUnit::GH, 0.0);
+        assert_eq!(
+            share_of_pool(profile.hash_rate_24h, stats.pool_24h_hash_rate),
+            0.0
+        );
+    }
+}
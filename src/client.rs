@@ -1,79 +1,1301 @@
 //! Client
 
-use reqwest::{Client, Response};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures_util::future;
+use futures_util::stream::Stream;
+use reqwest::header::HeaderValue;
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
 use serde::de::DeserializeOwned;
+use serde_json::Value;
 use url::Url;
 
-use crate::builder::BraiinsPoolClientBuilder;
+use crate::api_key::ApiKey;
+use crate::builder::{self, BraiinsPoolClientBuilder};
+use crate::dashboard::{Dashboard, PartialDashboard};
 use crate::error::Error;
-use crate::model::{BtcResponse, DailyRewards, PoolStats, UserProfile, Workers};
+#[cfg(feature = "fixtures")]
+use crate::fixtures::FixtureSet;
+use crate::lenient::{self, PartialResponse};
+use crate::meta::ResponseMeta;
+use crate::model::{
+    Currency, CurrencyResponse, DailyRewards, DailyRewardsPage, PoolStats, UserProfile, Worker,
+    WorkerState, Workers, WorkersDiff,
+};
+use crate::poll::{self, AdaptivePoll};
+use crate::rate_limit::RateLimiter;
+use crate::stats::{ClientStats, Counters};
+use crate::util;
 
 const BASE_URL: &str = "https://pool.braiins.com";
 
+/// Get the path of a currency endpoint (i.e. `/stats/json/btc`)
+#[inline]
+fn endpoint_path(endpoint: &str, currency: &Currency) -> String {
+    format!("{endpoint}/{}", currency.as_path_segment())
+}
+
 /// Braiins Pool client
+///
+/// Cloning is cheap and clones share the connection pool and the [stats](BraiinsPoolClient::stats),
+/// so there's no need to wrap the client in an [`Arc`] to share it between tasks.
+///
+/// There's no explicit close: the idle connections are closed when the last clone is dropped.
+/// Use [`BraiinsPoolClientBuilder::one_shot`] to close them after each request instead.
 #[derive(Debug, Clone)]
 pub struct BraiinsPoolClient {
     pub(crate) url: Url,
+    pub(crate) fallback_url: Option<Url>,
     pub(crate) client: Client,
+    /// Auth header added to each request, if not a default header of the client
+    auth_header: Option<HeaderValue>,
+    pub(crate) max_json_depth: Option<usize>,
+    pub(crate) rate_limiter: Option<Arc<RateLimiter>>,
+    counters: Arc<Counters>,
+    #[cfg(feature = "fixtures")]
+    fixtures: Option<Arc<FixtureSet>>,
 }
 
 impl BraiinsPoolClient {
     /// Construct a new Braiins Pool client
     pub fn new<T>(api_key: T) -> Result<Self, Error>
     where
-        T: Into<String>,
+        T: Into<ApiKey>,
     {
         Self::builder(api_key).build()
     }
 
+    /// Construct a new Braiins Pool client without API key
+    ///
+    /// See [`BraiinsPoolClientBuilder::anonymous`].
+    #[inline]
+    pub fn new_anonymous() -> Result<Self, Error> {
+        BraiinsPoolClientBuilder::anonymous().build()
+    }
+
     /// Construct a new Braiins Pool client builder
     #[inline]
     pub fn builder<T>(api_key: T) -> BraiinsPoolClientBuilder
     where
-        T: Into<String>,
+        T: Into<ApiKey>,
     {
         BraiinsPoolClientBuilder::new(api_key)
     }
 
     /// Construct new with a custom reqwest [`Client`].
+    ///
+    /// The client is used as is: the caller is responsible for the auth token (`Pool-Auth-Token` default header).
+    /// To let this crate add it, use [`BraiinsPoolClient::from_client_with_api_key`].
     #[inline]
     pub fn from_client(client: Client) -> Self {
         Self {
             url: Url::parse(BASE_URL).expect("Invalid base URL"),
+            fallback_url: None,
             client,
+            auth_header: None,
+            max_json_depth: None,
+            rate_limiter: None,
+            counters: Arc::default(),
+            #[cfg(feature = "fixtures")]
+            fixtures: None,
+        }
+    }
+
+    /// Construct new with a custom reqwest [`Client`], adding the auth token to each request
+    ///
+    /// Useful to share a preconfigured client (i.e. with a custom DNS resolver) between services.
+    pub fn from_client_with_api_key<T>(client: Client, api_key: T) -> Result<Self, Error>
+    where
+        T: Into<ApiKey>,
+    {
+        let api_key: ApiKey = api_key.into();
+
+        // Check API key
+        if !builder::is_valid_api_key(api_key.as_str()) {
+            return Err(Error::InvalidApiKey);
+        }
+
+        let mut auth_header: HeaderValue = HeaderValue::from_str(api_key.as_str())?;
+        auth_header.set_sensitive(true);
+
+        let mut client: Self = Self::from_client(client);
+        client.auth_header = Some(auth_header);
+        Ok(client)
+    }
+
+    /// Construct an offline client, returning the canned responses of the [`FixtureSet`]
+    ///
+    /// The modeled endpoints (and the methods built on them, like [`BraiinsPoolClient::dashboard`])
+    /// never hit the network, while the raw ones
+    /// ([`BraiinsPoolClient::get_currency`], [`BraiinsPoolClient::pool_stats_response`]) still do.
+    #[cfg(feature = "fixtures")]
+    pub fn from_fixtures(fixtures: FixtureSet) -> Self {
+        let mut client: Self = Self::from_client(Client::new());
+        client.fixtures = Some(Arc::new(fixtures));
+        client
+    }
+
+    /// Get the fixture of an endpoint, if the client is offline
+    #[cfg(feature = "fixtures")]
+    fn fixture<T, F>(&self, get: F) -> Option<Result<T, Error>>
+    where
+        F: FnOnce(&FixtureSet) -> Option<T>,
+    {
+        self.fixtures
+            .as_deref()
+            .map(|fixtures| get(fixtures).ok_or(Error::NotFound))
+    }
+
+    fn get(&self, url: Url) -> RequestBuilder {
+        let builder: RequestBuilder = self.client.get(url);
+
+        match &self.auth_header {
+            Some(auth_header) => builder.header("Pool-Auth-Token", auth_header.clone()),
+            None => builder,
+        }
+    }
+
+    async fn send(&self, path: &str) -> Result<Response, Error> {
+        let url: Url = self.url.join(path)?;
+
+        // Wait for the rate limiter, if any
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.acquire().await;
+        }
+
+        let res: Response = match self.get(url).send().await {
+            Ok(res) => res,
+            // Primary host unreachable: retry against the fallback one, if any
+            Err(e) if e.is_connect() => match &self.fallback_url {
+                Some(fallback_url) => {
+                    let url: Url = fallback_url.join(path)?;
+                    self.get(url).send().await?
+                }
+                None => return Err(Error::from(e)),
+            },
+            Err(e) => return Err(Error::from(e)),
+        };
+
+        match res.status() {
+            StatusCode::UNAUTHORIZED => Err(Error::Unauthorized),
+            StatusCode::NOT_FOUND => Err(Error::NotFound),
+            StatusCode::CONFLICT => Err(Error::Conflict),
+            StatusCode::UNPROCESSABLE_ENTITY => Err(Error::UnprocessableEntity),
+            StatusCode::TOO_MANY_REQUESTS => Err(Error::TooManyRequests),
+            status if status.is_client_error() => Err(Error::UnhandledClientError(status)),
+            status if status.is_server_error() => Err(Error::ServerError(status)),
+            _ => Ok(res),
+        }
+    }
+
+    async fn request<T>(&self, endpoint: &str, currency: &Currency) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+    {
+        let path: String = endpoint_path(endpoint, currency);
+        let result: Result<T, Error> = self.fetch(&path, currency).await;
+        self.counters.record(&result);
+        result
+    }
+
+    async fn request_with_meta<T>(
+        &self,
+        endpoint: &str,
+        currency: &Currency,
+    ) -> Result<(T, ResponseMeta), Error>
+    where
+        T: DeserializeOwned,
+    {
+        let path: String = endpoint_path(endpoint, currency);
+        let result: Result<(T, ResponseMeta), Error> = self.fetch_with_meta(&path, currency).await;
+        self.counters.record(&result);
+        result
+    }
+
+    /// Fetch the response, unwrapping the currency key
+    async fn fetch<T>(&self, path: &str, currency: &Currency) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+    {
+        self.fetch_with_meta(path, currency)
+            .await
+            .map(|(res, _)| res)
+    }
+
+    /// Fetch the response, unwrapping the currency key and keeping its metadata
+    async fn fetch_with_meta<T>(
+        &self,
+        path: &str,
+        currency: &Currency,
+    ) -> Result<(T, ResponseMeta), Error>
+    where
+        T: DeserializeOwned,
+    {
+        let res: Response = self.send(path).await?;
+        let meta: ResponseMeta = ResponseMeta {
+            status: res.status(),
+            headers: res.headers().clone(),
+        };
+        let body = res.bytes().await?;
+
+        // Check nesting depth
+        if let Some(max_depth) = self
+            .max_json_depth
+            .filter(|max_depth| util::json_depth_exceeds(&body, *max_depth))
+        {
+            return Err(Error::MaxJsonDepthExceeded(max_depth));
         }
+
+        let mut deserializer = serde_json::Deserializer::from_slice(&body);
+        let res: Result<CurrencyResponse<T>, serde_json::Error> =
+            CurrencyResponse::deserialize_for(&mut deserializer, currency)
+                .and_then(|res| deserializer.end().map(|_| res));
+        let res: CurrencyResponse<T> = res.map_err(|error| Error::FailedToDeserialize {
+            endpoint: path.to_string(),
+            snippet: util::json_error_snippet(&body, error.line(), error.column()),
+            error,
+        })?;
+
+        Ok((res.data, meta))
+    }
+
+    async fn request_lenient<T>(
+        &self,
+        endpoint: &str,
+        currency: &Currency,
+    ) -> Result<PartialResponse<T>, Error>
+    where
+        T: DeserializeOwned,
+    {
+        let path: String = endpoint_path(endpoint, currency);
+        let result: Result<PartialResponse<T>, Error> =
+            self.request_lenient_inner(&path, currency).await;
+        self.counters.record(&result);
+        result
+    }
+
+    async fn request_lenient_inner<T>(
+        &self,
+        path: &str,
+        currency: &Currency,
+    ) -> Result<PartialResponse<T>, Error>
+    where
+        T: DeserializeOwned,
+    {
+        let res: Value = self.fetch(path, currency).await?;
+        lenient::from_value_lenient(res).map_err(|error| Error::FailedToDeserialize {
+            endpoint: path.to_string(),
+            error,
+            snippet: None,
+        })
     }
 
-    async fn request<T>(&self, url: Url) -> Result<T, Error>
+    /// Get a custom endpoint, unwrapping the currency key
+    ///
+    /// The response must be `{ "<currency>": T }`-shaped (i.e. `{ "btc": T }`), like the ones of the modeled endpoints:
+    /// useful to type endpoints not (yet) supported by this crate.
+    ///
+    /// I.e. payouts aren't modeled, since their response shape isn't documented:
+    /// define a type matching the response and fetch it with this method.
+    pub async fn get_currency<T>(&self, path: &str, currency: Currency) -> Result<T, Error>
     where
         T: DeserializeOwned,
     {
-        let res: Response = self.client.get(url).send().await?;
-        let res: BtcResponse<T> = res.json().await?;
-        Ok(res.btc)
+        let result: Result<T, Error> = self.fetch(path, &currency).await;
+        self.counters.record(&result);
+        result
+    }
+
+    /// Get the client stats
+    ///
+    /// Counters are shared between the clones of the client.
+    #[inline]
+    pub fn stats(&self) -> ClientStats {
+        self.counters.snapshot()
     }
 
     /// Get pool stats
+    #[inline]
     pub async fn pool_stats(&self) -> Result<PoolStats, Error> {
-        let url: Url = self.url.join("/stats/json/btc")?;
-        self.request(url).await
+        self.pool_stats_for(Currency::Btc).await
+    }
+
+    /// Get the raw pool stats response, after the status code handling
+    ///
+    /// The caller owns the parsing of the body: useful to stream it instead of buffering it.
+    /// The response is `{ "btc": PoolStats }`-shaped.
+    pub async fn pool_stats_response(&self) -> Result<Response, Error> {
+        let path: String = endpoint_path("/stats/json", &Currency::Btc);
+        let result: Result<Response, Error> = self.send(&path).await;
+        self.counters.record(&result);
+        result
+    }
+
+    /// Get pool stats, with the response metadata (i.e. rate limit headers)
+    ///
+    /// Offline clients return a default (`200 OK`, no headers) [`ResponseMeta`].
+    pub async fn pool_stats_with_meta(&self) -> Result<(PoolStats, ResponseMeta), Error> {
+        #[cfg(feature = "fixtures")]
+        if let Some(res) = self.fixture(|f| f.pool_stats.clone()) {
+            return res.map(|res| (res, ResponseMeta::default()));
+        }
+
+        self.request_with_meta("/stats/json", &Currency::Btc).await
+    }
+
+    /// Get pool stats, repairing the fields that don't match the model
+    ///
+    /// See [`lenient::from_value_lenient`].
+    pub async fn pool_stats_lenient(&self) -> Result<PartialResponse<PoolStats>, Error> {
+        #[cfg(feature = "fixtures")]
+        if let Some(res) = self.fixture(|f| f.pool_stats.clone()) {
+            return res.map(PartialResponse::complete);
+        }
+
+        self.request_lenient("/stats/json", &Currency::Btc).await
+    }
+
+    /// Stream pool stats, polled right away and then shortly after each expected update
+    ///
+    /// The update cadence is learned by the `scheduler` (see [`AdaptivePoll`]).
+    /// Errors are yielded without ending the stream: the next poll is scheduled as if nothing was received.
+    pub fn pool_stats_adaptive_stream(
+        &self,
+        scheduler: AdaptivePoll,
+    ) -> impl Stream<Item = Result<PoolStats, Error>> + '_ {
+        poll::adaptive_stream(scheduler, move || self.pool_stats(), poll::unix_now)
+    }
+
+    /// Get pool stats of a currency
+    ///
+    /// Offline clients only have the [`Currency::Btc`] fixture: the other currencies return [`Error::NotFound`].
+    pub async fn pool_stats_for(&self, currency: Currency) -> Result<PoolStats, Error> {
+        #[cfg(feature = "fixtures")]
+        if let Some(res) = self.fixture(|f| match currency {
+            Currency::Btc => f.pool_stats.clone(),
+            _ => None,
+        }) {
+            return res;
+        }
+
+        self.request("/stats/json", &currency).await
+    }
+
+    /// Get pool stats of many currencies, concurrently
+    ///
+    /// Fails fast on the first failing currency:
+    /// use [`BraiinsPoolClient::pool_stats_multi_partial`] to keep the successful ones.
+    pub async fn pool_stats_multi(
+        &self,
+        currencies: &[Currency],
+    ) -> Result<HashMap<Currency, PoolStats>, Error> {
+        let currencies: HashSet<&Currency> = currencies.iter().collect();
+        let stats: Vec<(Currency, PoolStats)> =
+            future::try_join_all(currencies.into_iter().map(|currency| async move {
+                let stats: PoolStats = self.pool_stats_for(currency.clone()).await?;
+                Ok::<_, Error>((currency.clone(), stats))
+            }))
+            .await?;
+        Ok(stats.into_iter().collect())
+    }
+
+    /// Get pool stats of many currencies, concurrently, keeping the result of every currency
+    pub async fn pool_stats_multi_partial(
+        &self,
+        currencies: &[Currency],
+    ) -> HashMap<Currency, Result<PoolStats, Error>> {
+        let currencies: HashSet<&Currency> = currencies.iter().collect();
+        future::join_all(currencies.into_iter().map(|currency| async move {
+            (
+                currency.clone(),
+                self.pool_stats_for(currency.clone()).await,
+            )
+        }))
+        .await
+        .into_iter()
+        .collect()
     }
 
     /// Get user profile
     pub async fn user_profile(&self) -> Result<UserProfile, Error> {
-        let url: Url = self.url.join("/accounts/profile/json/btc")?;
-        self.request(url).await
+        #[cfg(feature = "fixtures")]
+        if let Some(res) = self.fixture(|f| f.user_profile.clone()) {
+            return res;
+        }
+
+        self.request("/accounts/profile/json", &Currency::Btc).await
+    }
+
+    /// Get user profile, with the response metadata (i.e. rate limit headers)
+    ///
+    /// Offline clients return a default (`200 OK`, no headers) [`ResponseMeta`].
+    pub async fn user_profile_with_meta(&self) -> Result<(UserProfile, ResponseMeta), Error> {
+        #[cfg(feature = "fixtures")]
+        if let Some(res) = self.fixture(|f| f.user_profile.clone()) {
+            return res.map(|res| (res, ResponseMeta::default()));
+        }
+
+        self.request_with_meta("/accounts/profile/json", &Currency::Btc)
+            .await
+    }
+
+    /// Get user profile, repairing the fields that don't match the model
+    ///
+    /// See [`lenient::from_value_lenient`].
+    pub async fn user_profile_lenient(&self) -> Result<PartialResponse<UserProfile>, Error> {
+        #[cfg(feature = "fixtures")]
+        if let Some(res) = self.fixture(|f| f.user_profile.clone()) {
+            return res.map(PartialResponse::complete);
+        }
+
+        self.request_lenient("/accounts/profile/json", &Currency::Btc)
+            .await
+    }
+
+    /// Check if the API key is valid
+    ///
+    /// Fetches the user profile (`/accounts/profile/json/btc`), so it costs one request against the rate limit.
+    /// Returns `Ok(false)` if the key is rejected and propagates any other error.
+    pub async fn verify_credentials(&self) -> Result<bool, Error> {
+        match self.user_profile().await {
+            Ok(..) => Ok(true),
+            Err(Error::InvalidApiKey | Error::Unauthorized) => Ok(false),
+            Err(e) => Err(e),
+        }
     }
 
     /// Get daily rewards
     pub async fn daily_rewards(&self) -> Result<DailyRewards, Error> {
-        let url: Url = self.url.join("/accounts/rewards/json/btc")?;
-        self.request(url).await
+        #[cfg(feature = "fixtures")]
+        if let Some(res) = self.fixture(FixtureSet::get_daily_rewards) {
+            return res;
+        }
+
+        self.request("/accounts/rewards/json", &Currency::Btc).await
+    }
+
+    /// Get daily rewards, with the response metadata (i.e. rate limit headers)
+    ///
+    /// Offline clients return a default (`200 OK`, no headers) [`ResponseMeta`].
+    pub async fn daily_rewards_with_meta(&self) -> Result<(DailyRewards, ResponseMeta), Error> {
+        #[cfg(feature = "fixtures")]
+        if let Some(res) = self.fixture(FixtureSet::get_daily_rewards) {
+            return res.map(|res| (res, ResponseMeta::default()));
+        }
+
+        self.request_with_meta("/accounts/rewards/json", &Currency::Btc)
+            .await
+    }
+
+    /// Get daily rewards, repairing the fields that don't match the model
+    ///
+    /// See [`lenient::from_value_lenient`].
+    pub async fn daily_rewards_lenient(&self) -> Result<PartialResponse<DailyRewards>, Error> {
+        #[cfg(feature = "fixtures")]
+        if let Some(res) = self.fixture(FixtureSet::get_daily_rewards) {
+            return res.map(PartialResponse::complete);
+        }
+
+        self.request_lenient("/accounts/rewards/json", &Currency::Btc)
+            .await
+    }
+
+    /// Get a page of `limit` daily rewards, starting at `offset`
+    ///
+    /// The API doesn't paginate: the whole history is fetched and sliced client-side.
+    /// To walk all the pages with a single request, use [`DailyRewards::pages`].
+    pub async fn daily_rewards_paged(
+        &self,
+        offset: u32,
+        limit: u32,
+    ) -> Result<DailyRewardsPage, Error> {
+        let rewards: DailyRewards = self.daily_rewards().await?;
+        Ok(rewards.page(offset, limit))
     }
 
     /// Get workers
     pub async fn workers(&self) -> Result<Workers, Error> {
-        let url: Url = self.url.join("/accounts/workers/json/btc")?;
-        self.request(url).await
+        #[cfg(feature = "fixtures")]
+        if let Some(res) = self.fixture(|f| f.workers.clone()) {
+            return res;
+        }
+
+        self.request("/accounts/workers/json", &Currency::Btc).await
+    }
+
+    /// Get workers, with the response metadata (i.e. rate limit headers)
+    ///
+    /// Offline clients return a default (`200 OK`, no headers) [`ResponseMeta`].
+    pub async fn workers_with_meta(&self) -> Result<(Workers, ResponseMeta), Error> {
+        #[cfg(feature = "fixtures")]
+        if let Some(res) = self.fixture(|f| f.workers.clone()) {
+            return res.map(|res| (res, ResponseMeta::default()));
+        }
+
+        self.request_with_meta("/accounts/workers/json", &Currency::Btc)
+            .await
+    }
+
+    /// Get workers, repairing the fields that don't match the model
+    ///
+    /// See [`lenient::from_value_lenient`].
+    pub async fn workers_lenient(&self) -> Result<PartialResponse<Workers>, Error> {
+        #[cfg(feature = "fixtures")]
+        if let Some(res) = self.fixture(|f| f.workers.clone()) {
+            return res.map(PartialResponse::complete);
+        }
+
+        self.request_lenient("/accounts/workers/json", &Currency::Btc)
+            .await
+    }
+
+    /// Get the workers that need attention
+    ///
+    /// Returns the workers in the `low` or `off` state, or that haven't submitted a share for more than `max_share_age`,
+    /// sorted by name.
+    pub async fn unhealthy_workers(
+        &self,
+        max_share_age: Duration,
+    ) -> Result<Vec<(String, Worker)>, Error> {
+        let workers: Workers = self.workers().await?;
+
+        let mut unhealthy: Vec<(String, Worker)> = workers
+            .workers
+            .into_iter()
+            .filter(|(_, worker)| {
+                matches!(worker.state, WorkerState::Low | WorkerState::Off)
+                    || worker.is_stale(max_share_age)
+            })
+            .collect();
+        unhealthy.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        Ok(unhealthy)
+    }
+
+    /// Fetch the workers and diff them against a `previous` snapshot
+    ///
+    /// See [`Workers::diff`].
+    pub async fn workers_changed_since(&self, previous: &Workers) -> Result<WorkersDiff, Error> {
+        let workers: Workers = self.workers().await?;
+        Ok(workers.diff(previous))
+    }
+
+    /// Get pool stats, user profile and workers, concurrently
+    ///
+    /// Fails if any of the endpoints fails: use [`BraiinsPoolClient::dashboard_partial`] to keep the successful ones.
+    #[inline]
+    pub async fn dashboard(&self) -> Result<Dashboard, Error> {
+        Dashboard::try_from(self.dashboard_partial().await)
+    }
+
+    /// Get pool stats, user profile and workers, concurrently, keeping the result of every endpoint
+    pub async fn dashboard_partial(&self) -> PartialDashboard {
+        let (pool_stats, user_profile, workers) =
+            future::join3(self.pool_stats(), self.user_profile(), self.workers()).await;
+
+        PartialDashboard {
+            pool_stats,
+            user_profile,
+            workers,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::*;
+    use crate::error::ErrorKind;
+    use crate::test_util::{POOL_STATS, USER_PROFILE, WORKERS, btc, patched};
+
+    async fn mock(endpoint: &str, response: ResponseTemplate) -> (MockServer, BraiinsPoolClient) {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(endpoint))
+            .and(header("Pool-Auth-Token", "apikey"))
+            .respond_with(response)
+            .mount(&server)
+            .await;
+
+        let client = BraiinsPoolClient::builder("apikey")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+
+        (server, client)
+    }
+
+    #[test]
+    fn test_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+
+        // Clones share the underlying connection pool and counters, so they can be moved into many tasks
+        assert_send_sync::<BraiinsPoolClient>();
+        assert_send_sync::<BraiinsPoolClientBuilder>();
+    }
+
+    #[test]
+    fn test_new() {
+        let client = BraiinsPoolClient::new("apikey").unwrap();
+        assert_eq!(client.url.as_str(), "https://pool.braiins.com/");
+        assert!(client.fallback_url.is_none());
+
+        let api_key: String = String::from("apikey");
+        assert!(BraiinsPoolClient::new(api_key).is_ok());
+
+        assert!(matches!(
+            BraiinsPoolClient::new(""),
+            Err(Error::InvalidApiKey)
+        ));
+    }
+
+    fn json(body: &str) -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_raw(body, "application/json")
+    }
+
+    #[tokio::test]
+    async fn test_pool_stats() {
+        let (_server, client) = mock("/stats/json/btc", json(&btc(POOL_STATS))).await;
+        let stats = client.pool_stats().await.unwrap();
+        assert_eq!(stats.update_ts, 1699938300);
+        assert!(stats.blocks.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_pool_stats_response() {
+        let (_server, client) = mock("/stats/json/btc", json(&btc(POOL_STATS))).await;
+        let mut res: Response = client.pool_stats_response().await.unwrap();
+
+        let mut body: Vec<u8> = Vec::new();
+        while let Some(chunk) = res.chunk().await.unwrap() {
+            body.extend_from_slice(&chunk);
+        }
+
+        let stats: CurrencyResponse<PoolStats> = serde_json::from_reader(body.as_slice()).unwrap();
+        assert_eq!(stats.data.update_ts, 1699938300);
+
+        let (_server, client) = mock("/stats/json/btc", ResponseTemplate::new(401)).await;
+        let err = client.pool_stats_response().await.unwrap_err();
+        assert!(matches!(err, Error::Unauthorized));
+    }
+
+    #[tokio::test]
+    async fn test_user_profile() {
+        let (_server, client) = mock("/accounts/profile/json/btc", json(&btc(USER_PROFILE))).await;
+        let profile = client.user_profile().await.unwrap();
+        assert_eq!(profile.ok_workers, 2);
+        assert_eq!(profile.current_balance, 0.15);
+    }
+
+    #[tokio::test]
+    async fn test_daily_rewards() {
+        let body = r#"{
+    "btc": {
+        "daily_rewards": [
+            {
+                "date": 1699920000,
+                "total_reward": "0.00110000",
+                "mining_reward": "0.00100000",
+                "bos_plus_reward": "0.00010000",
+                "referral_bonus": "0.00000000",
+                "referral_reward": "0.00000000",
+                "calculation_date": 1700006400
+            }
+        ]
+    }
+}"#;
+        let (_server, client) = mock("/accounts/rewards/json/btc", json(body)).await;
+        let rewards = client.daily_rewards().await.unwrap();
+        assert_eq!(rewards.daily_rewards.len(), 1);
+        assert_eq!(rewards.daily_rewards[0].total_reward, 0.0011);
+    }
+
+    #[tokio::test]
+    async fn test_daily_rewards_paged() {
+        let rewards: Vec<String> = (0..3)
+            .map(|i| {
+                format!(
+                    r#"{{
+                "date": {},
+                "total_reward": "0.00110000",
+                "mining_reward": "0.00100000",
+                "bos_plus_reward": "0.00010000",
+                "referral_bonus": "0.00000000",
+                "referral_reward": "0.00000000",
+                "calculation_date": 1700006400
+            }}"#,
+                    1699920000 + i * 86400
+                )
+            })
+            .collect();
+        let body: String = format!(r#"{{"btc": {{"daily_rewards": [{}]}}}}"#, rewards.join(","));
+        let (_server, client) = mock("/accounts/rewards/json/btc", json(&body)).await;
+
+        let page = client.daily_rewards_paged(0, 2).await.unwrap();
+        assert_eq!(page.daily_rewards.len(), 2);
+        assert!(page.has_more);
+
+        let page = client.daily_rewards_paged(2, 2).await.unwrap();
+        assert_eq!(page.daily_rewards.len(), 1);
+        assert_eq!(page.daily_rewards[0].date, 1699920000 + 2 * 86400);
+        assert!(!page.has_more);
+    }
+
+    #[tokio::test]
+    async fn test_workers() {
+        let (_server, client) = mock("/accounts/workers/json/btc", json(&btc(WORKERS))).await;
+        let workers = client.workers().await.unwrap();
+        assert!(workers.workers.contains_key("username.worker1"));
+    }
+
+    #[tokio::test]
+    async fn test_status_code_mapping() {
+        let (_server, client) = mock("/stats/json/btc", ResponseTemplate::new(401)).await;
+        let err = client.pool_stats().await.unwrap_err();
+        assert!(matches!(err, Error::Unauthorized));
+
+        let (_server, client) = mock("/stats/json/btc", ResponseTemplate::new(404)).await;
+        let err = client.pool_stats().await.unwrap_err();
+        assert!(matches!(err, Error::NotFound));
+
+        let (_server, client) = mock("/stats/json/btc", ResponseTemplate::new(409)).await;
+        let err = client.pool_stats().await.unwrap_err();
+        assert!(matches!(err, Error::Conflict));
+
+        let (_server, client) = mock("/stats/json/btc", ResponseTemplate::new(422)).await;
+        let err = client.pool_stats().await.unwrap_err();
+        assert!(matches!(err, Error::UnprocessableEntity));
+
+        let (_server, client) = mock("/stats/json/btc", ResponseTemplate::new(429)).await;
+        let err = client.pool_stats().await.unwrap_err();
+        assert!(matches!(err, Error::TooManyRequests));
+
+        let (_server, client) = mock("/stats/json/btc", ResponseTemplate::new(418)).await;
+        let err = client.pool_stats().await.unwrap_err();
+        assert!(matches!(
+            err,
+            Error::UnhandledClientError(StatusCode::IM_A_TEAPOT)
+        ));
+
+        let (_server, client) = mock("/stats/json/btc", ResponseTemplate::new(503)).await;
+        let err = client.pool_stats().await.unwrap_err();
+        assert!(matches!(
+            err,
+            Error::ServerError(StatusCode::SERVICE_UNAVAILABLE)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_verify_credentials() {
+        let (_server, client) = mock("/accounts/profile/json/btc", json(&btc(USER_PROFILE))).await;
+        assert!(client.verify_credentials().await.unwrap());
+
+        let (_server, client) =
+            mock("/accounts/profile/json/btc", ResponseTemplate::new(401)).await;
+        assert!(!client.verify_credentials().await.unwrap());
+
+        let (_server, client) =
+            mock("/accounts/profile/json/btc", ResponseTemplate::new(500)).await;
+        let err = client.verify_credentials().await.unwrap_err();
+        assert!(matches!(err, Error::ServerError(..)));
+    }
+
+    #[tokio::test]
+    async fn test_fallback_base_url() {
+        // Bind and drop a listener to get a port with nothing listening on it
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let primary: String = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+
+        let fallback = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/accounts/profile/json/btc"))
+            .respond_with(json(&btc(USER_PROFILE)))
+            .mount(&fallback)
+            .await;
+
+        let client = BraiinsPoolClient::builder("apikey")
+            .base_url(primary)
+            .fallback_base_url(fallback.uri())
+            .build()
+            .unwrap();
+
+        let profile = client.user_profile().await.unwrap();
+        assert_eq!(profile.ok_workers, 2);
+    }
+
+    #[tokio::test]
+    async fn test_dashboard_partial() {
+        let server = MockServer::start().await;
+        for (endpoint, response) in [
+            ("/stats/json/btc", json(&btc(POOL_STATS))),
+            ("/accounts/profile/json/btc", ResponseTemplate::new(500)),
+            ("/accounts/workers/json/btc", json(&btc(WORKERS))),
+        ] {
+            Mock::given(method("GET"))
+                .and(path(endpoint))
+                .respond_with(response)
+                .mount(&server)
+                .await;
+        }
+
+        let client = BraiinsPoolClient::builder("apikey")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+
+        let dashboard = client.dashboard_partial().await;
+        assert!(!dashboard.is_complete());
+        assert_eq!(dashboard.pool_stats.unwrap().update_ts, 1699938300);
+        assert!(matches!(
+            dashboard.user_profile,
+            Err(Error::ServerError(StatusCode::INTERNAL_SERVER_ERROR))
+        ));
+        assert_eq!(dashboard.workers.unwrap().workers.len(), 2);
+
+        let err = client.dashboard().await.unwrap_err();
+        assert!(matches!(err, Error::ServerError(..)));
+    }
+
+    #[tokio::test]
+    async fn test_pool_stats_multi() {
+        let ltc: String = format!(
+            r#"{{"ltc": {}}}"#,
+            POOL_STATS.replacen("1699938300", "1699938400", 1)
+        );
+        let server = MockServer::start().await;
+        for (endpoint, response) in [
+            ("/stats/json/btc", json(&btc(POOL_STATS))),
+            ("/stats/json/ltc", json(&ltc)),
+            ("/stats/json/doge", ResponseTemplate::new(404)),
+        ] {
+            Mock::given(method("GET"))
+                .and(path(endpoint))
+                .respond_with(response)
+                .mount(&server)
+                .await;
+        }
+
+        let client = BraiinsPoolClient::builder("apikey")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+
+        let btc: Currency = Currency::Btc;
+        let ltc: Currency = "ltc".parse().unwrap();
+        let doge: Currency = "doge".parse().unwrap();
+
+        let stats = client
+            .pool_stats_multi(&[btc.clone(), ltc.clone(), btc.clone()])
+            .await
+            .unwrap();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[&btc].update_ts, 1699938300);
+        assert_eq!(stats[&ltc].update_ts, 1699938400);
+
+        let err = client
+            .pool_stats_multi(&[btc.clone(), doge.clone()])
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::NotFound));
+
+        let stats = client
+            .pool_stats_multi_partial(&[btc.clone(), ltc.clone(), doge.clone()])
+            .await;
+        assert_eq!(stats.len(), 3);
+        assert_eq!(stats[&btc].as_ref().unwrap().update_ts, 1699938300);
+        assert_eq!(stats[&ltc].as_ref().unwrap().update_ts, 1699938400);
+        assert!(matches!(stats[&doge], Err(Error::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn test_dashboard_concurrent() {
+        let delay: Duration = Duration::from_millis(300);
+        let server = MockServer::start().await;
+        for (endpoint, body) in [
+            ("/stats/json/btc", btc(POOL_STATS)),
+            ("/accounts/profile/json/btc", btc(USER_PROFILE)),
+            ("/accounts/workers/json/btc", btc(WORKERS)),
+        ] {
+            Mock::given(method("GET"))
+                .and(path(endpoint))
+                .respond_with(json(&body).set_delay(delay))
+                .mount(&server)
+                .await;
+        }
+
+        let client = BraiinsPoolClient::builder("apikey")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+
+        // Serial requests would take at least 3 delays
+        let start = std::time::Instant::now();
+        client.dashboard().await.unwrap();
+        assert!(start.elapsed() < delay * 3);
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "fixtures")]
+    async fn test_from_fixtures() {
+        let pool_stats: CurrencyResponse<PoolStats> =
+            serde_json::from_str(&btc(POOL_STATS)).unwrap();
+        let workers: CurrencyResponse<Workers> = serde_json::from_str(&btc(WORKERS)).unwrap();
+        let fixtures = FixtureSet::new()
+            .pool_stats(pool_stats.data.clone())
+            .workers(workers.data.clone())
+            .daily_rewards(Vec::new());
+
+        let client = BraiinsPoolClient::from_fixtures(fixtures);
+        assert_eq!(client.pool_stats().await.unwrap(), pool_stats.data);
+        assert_eq!(client.workers().await.unwrap(), workers.data);
+        assert!(
+            client
+                .daily_rewards()
+                .await
+                .unwrap()
+                .daily_rewards
+                .is_empty()
+        );
+        assert!(matches!(client.user_profile().await, Err(Error::NotFound)));
+
+        // Never hits the network
+        let ltc: Currency = "ltc".parse().unwrap();
+        assert!(matches!(
+            client.pool_stats_for(ltc.clone()).await,
+            Err(Error::NotFound)
+        ));
+        let stats = client
+            .pool_stats_multi_partial(&[Currency::Btc, ltc.clone()])
+            .await;
+        assert_eq!(stats[&Currency::Btc].as_ref().unwrap(), &pool_stats.data);
+        assert!(matches!(stats[&ltc], Err(Error::NotFound)));
+
+        let dashboard = client.dashboard_partial().await;
+        assert!(dashboard.pool_stats.is_ok());
+        assert!(dashboard.user_profile.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_stats() {
+        let server = MockServer::start().await;
+        for (endpoint, response) in [
+            ("/stats/json/btc", json(&btc(POOL_STATS))),
+            ("/accounts/profile/json/btc", ResponseTemplate::new(500)),
+            ("/accounts/workers/json/btc", ResponseTemplate::new(429)),
+        ] {
+            Mock::given(method("GET"))
+                .and(path(endpoint))
+                .respond_with(response)
+                .mount(&server)
+                .await;
+        }
+
+        let client = BraiinsPoolClient::builder("apikey")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        assert_eq!(client.stats(), ClientStats::default());
+
+        client.pool_stats().await.unwrap();
+        client.pool_stats().await.unwrap();
+        client.user_profile().await.unwrap_err();
+        client.clone().workers().await.unwrap_err();
+
+        let stats = client.stats();
+        assert_eq!(stats.requests, 4);
+        assert_eq!(stats.successes, 2);
+        assert_eq!(stats.errors(), 2);
+        assert_eq!(stats.errors_by_kind.get(&ErrorKind::ServerError), Some(&1));
+        assert_eq!(
+            stats.errors_by_kind.get(&ErrorKind::TooManyRequests),
+            Some(&1)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unhealthy_workers() {
+        let now: u64 = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let worker = |state: &str, last_share: u64| {
+            format!(
+                r#"{{
+                "state": "{state}",
+                "last_share": {last_share},
+                "hash_rate_unit": "Gh/s",
+                "hash_rate_scoring": 15342,
+                "hash_rate_5m": 14977,
+                "hash_rate_60m": 15302,
+                "hash_rate_24h": 15351,
+                "shares_5m": 90304,
+                "shares_60m": 1125762,
+                "shares_24h": 20945364
+            }}"#
+            )
+        };
+        let body: String = format!(
+            r#"{{"btc": {{"workers": {{
+                "username.ok": {},
+                "username.low": {},
+                "username.off": {},
+                "username.stale": {},
+                "username.dis": {}
+            }}}}}}"#,
+            worker("ok", now - 60),
+            worker("low", now - 60),
+            worker("off", now - 60),
+            worker("ok", now - 3600),
+            worker("dis", now - 60),
+        );
+
+        let (_server, client) = mock("/accounts/workers/json/btc", json(&body)).await;
+        let unhealthy = client
+            .unhealthy_workers(Duration::from_secs(600))
+            .await
+            .unwrap();
+        let names: Vec<&str> = unhealthy.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["username.low", "username.off", "username.stale"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_from_client_with_api_key() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/stats/json/btc"))
+            .and(header("Pool-Auth-Token", "apikey"))
+            .respond_with(json(&btc(POOL_STATS)))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut client =
+            BraiinsPoolClient::from_client_with_api_key(Client::new(), "apikey").unwrap();
+        client.url = Url::parse(&server.uri()).unwrap();
+        assert!(!format!("{client:?}").contains("apikey"));
+
+        let stats = client.pool_stats().await.unwrap();
+        assert_eq!(stats.update_ts, 1699938300);
+        server.verify().await;
+
+        // Without the auth token
+        let mut client = BraiinsPoolClient::from_client(Client::new());
+        client.url = Url::parse(&server.uri()).unwrap();
+        assert!(client.pool_stats().await.is_err());
+
+        assert!(matches!(
+            BraiinsPoolClient::from_client_with_api_key(Client::new(), ""),
+            Err(Error::InvalidApiKey)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_with_meta() {
+        let (_server, client) = mock(
+            "/stats/json/btc",
+            json(&btc(POOL_STATS)).insert_header("X-RateLimit-Remaining", "17"),
+        )
+        .await;
+
+        let (stats, meta) = client.pool_stats_with_meta().await.unwrap();
+        assert_eq!(stats.update_ts, 1699938300);
+        assert_eq!(meta.status, StatusCode::OK);
+        assert_eq!(meta.rate_limit_remaining(), Some(17));
+        assert_eq!(meta.header("x-ratelimit-remaining"), Some("17"));
+        assert_eq!(client.stats().successes, 1);
+
+        // Simple methods unchanged
+        assert_eq!(client.pool_stats().await.unwrap(), stats);
+    }
+
+    #[tokio::test]
+    async fn test_lenient() {
+        // `update_ts` became a string and `fpps_rate` an object
+        let stats: Value = patched(
+            POOL_STATS,
+            serde_json::json!({"update_ts": "1699938300", "fpps_rate": {"value": 0.00000241}}),
+        );
+        let (_server, client) = mock("/stats/json/btc", json(&btc(&stats.to_string()))).await;
+        assert!(matches!(
+            client.pool_stats().await,
+            Err(Error::FailedToDeserialize { .. })
+        ));
+
+        let res = client.pool_stats_lenient().await.unwrap();
+        assert!(!res.is_complete());
+        assert_eq!(res.value.update_ts, 1699938300);
+        assert_eq!(res.value.fpps_rate, 0.0);
+        let mut paths: Vec<&str> = res.warnings.iter().map(|w| w.path.as_str()).collect();
+        paths.sort();
+        assert_eq!(paths, vec!["fpps_rate", "update_ts"]);
+
+        let stats = client.stats();
+        assert_eq!(stats.successes, 1);
+        assert_eq!(stats.errors(), 1);
+
+        // Matching response
+        let (_server, client) = mock("/accounts/workers/json/btc", json(&btc(WORKERS))).await;
+        let res = client.workers_lenient().await.unwrap();
+        assert!(res.is_complete());
+        assert!(res.value.workers.contains_key("username.worker1"));
+    }
+
+    #[tokio::test]
+    async fn test_workers_changed_since() {
+        let worker = |state: &str| {
+            format!(
+                r#"{{
+                "state": "{state}",
+                "last_share": 1542103204,
+                "hash_rate_unit": "Gh/s",
+                "hash_rate_scoring": 15342,
+                "hash_rate_5m": 14977,
+                "hash_rate_60m": 15302,
+                "hash_rate_24h": 15351
+            }}"#
+            )
+        };
+        let previous: String = format!(
+            r#"{{"workers": {{
+                "username.kept": {},
+                "username.down": {},
+                "username.gone": {}
+            }}}}"#,
+            worker("ok"),
+            worker("ok"),
+            worker("ok"),
+        );
+        let previous: Workers = serde_json::from_str(&previous).unwrap();
+        let body: String = format!(
+            r#"{{"btc": {{"workers": {{
+                "username.kept": {},
+                "username.down": {},
+                "username.new": {}
+            }}}}}}"#,
+            worker("ok"),
+            worker("off"),
+            worker("low"),
+        );
+
+        let (_server, client) = mock("/accounts/workers/json/btc", json(&body)).await;
+        let diff = client.workers_changed_since(&previous).await.unwrap();
+        assert_eq!(
+            diff,
+            WorkersDiff {
+                added: vec![String::from("username.new")],
+                removed: vec![String::from("username.gone")],
+                changed: vec![(
+                    String::from("username.down"),
+                    WorkerState::Ok,
+                    WorkerState::Off
+                )],
+            }
+        );
+
+        let (_server, client) = mock("/accounts/workers/json/btc", json(&body)).await;
+        let current: Workers = client.workers().await.unwrap();
+        assert!(
+            client
+                .workers_changed_since(&current)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_max_json_depth() {
+        let body: String = format!(
+            r#"{{"btc": {{"workers": {{}}, "extra": {}0{}}}}}"#,
+            "[".repeat(64),
+            "]".repeat(64)
+        );
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/accounts/workers/json/btc"))
+            .respond_with(json(&body))
+            .mount(&server)
+            .await;
+
+        let client = BraiinsPoolClient::builder("apikey")
+            .base_url(server.uri())
+            .max_json_depth(32)
+            .build()
+            .unwrap();
+        let err = client.workers().await.unwrap_err();
+        assert!(matches!(err, Error::MaxJsonDepthExceeded(32)));
+
+        let client = BraiinsPoolClient::builder("apikey")
+            .base_url(server.uri())
+            .max_json_depth(128)
+            .build()
+            .unwrap();
+        assert!(client.workers().await.unwrap().workers.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_failed_to_deserialize() {
+        let mut stats: Value = serde_json::from_str(POOL_STATS).unwrap();
+        stats.as_object_mut().unwrap().remove("fpps_rate");
+        let (_server, client) = mock("/stats/json/btc", json(&btc(&stats.to_string()))).await;
+        let err = client.pool_stats().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::FailedToDeserialize);
+        let msg: String = err.to_string();
+        assert!(msg.contains("/stats/json/btc"));
+        assert!(msg.contains("missing field `fpps_rate`"));
+
+        let (_server, client) =
+            mock("/stats/json/btc", json(r#"{"btc": {"hash_rate_unit": }}"#)).await;
+        let err = client.pool_stats().await.unwrap_err();
+        match err {
+            Error::FailedToDeserialize {
+                endpoint, snippet, ..
+            } => {
+                assert_eq!(endpoint, "/stats/json/btc");
+                assert!(snippet.unwrap().contains(r#""hash_rate_unit": }"#));
+            }
+            e => panic!("unexpected error: {e}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_currency() {
+        #[derive(Debug, serde::Deserialize)]
+        struct Custom {
+            value: u64,
+        }
+
+        let body = r#"{"username": "username", "btc": {"value": 42}}"#;
+        let (_server, client) = mock("/custom/json/btc", json(body)).await;
+        let custom: Custom = client
+            .get_currency("/custom/json/btc", Currency::Btc)
+            .await
+            .unwrap();
+        assert_eq!(custom.value, 42);
+
+        let (_server, client) = mock("/custom/json/btc", json(r#"{"ltc": {}}"#)).await;
+        let err = client
+            .get_currency::<Custom>("/custom/json/btc", Currency::Btc)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::FailedToDeserialize);
+        assert!(err.to_string().contains("missing currency key `btc`"));
+
+        // Currency not enumerated by the crate
+        let (_server, client) = mock("/custom/json/ltc", json(r#"{"ltc": {"value": 7}}"#)).await;
+        let custom: Custom = client
+            .get_currency("/custom/json/ltc", "ltc".parse::<Currency>().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(custom.value, 7);
     }
 }
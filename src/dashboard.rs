@@ -0,0 +1,113 @@
+//! Dashboard
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::error::Error;
+use crate::model::{PoolStats, UserProfile, WorkerState, Workers};
+
+/// Dashboard
+///
+/// Pool stats, user profile and workers, fetched together.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dashboard {
+    /// Pool stats
+    pub pool_stats: PoolStats,
+    /// User profile
+    pub user_profile: UserProfile,
+    /// Workers
+    pub workers: Workers,
+}
+
+impl fmt::Display for Dashboard {
+    /// Compact status report
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Pool hashrate: {:.2} (5m), {:.2} (24h)",
+            self.pool_stats.pool_5m_hash_rate.scaled(),
+            self.pool_stats.pool_24h_hash_rate.scaled()
+        )?;
+        writeln!(
+            f,
+            "My hashrate: {:.2} (5m), {:.2} (24h)",
+            self.user_profile.hash_rate_5m.scaled(),
+            self.user_profile.hash_rate_24h.scaled()
+        )?;
+        writeln!(
+            f,
+            "Balance: {:.8} BTC (today: {:.8} BTC)",
+            self.user_profile.current_balance, self.user_profile.today_reward
+        )?;
+
+        let counts: HashMap<WorkerState, usize> = self.workers.count_by_state();
+        let count = |state: WorkerState| counts.get(&state).copied().unwrap_or(0);
+        write!(
+            f,
+            "Workers: {} ok, {} low, {} off, {} disabled",
+            count(WorkerState::Ok),
+            count(WorkerState::Low),
+            count(WorkerState::Off),
+            count(WorkerState::Disabled)
+        )
+    }
+}
+
+/// Partial dashboard
+///
+/// Like [`Dashboard`], but keeps the result of every endpoint,
+/// so a failure on one of them doesn't lose the others.
+#[derive(Debug)]
+pub struct PartialDashboard {
+    /// Pool stats
+    pub pool_stats: Result<PoolStats, Error>,
+    /// User profile
+    pub user_profile: Result<UserProfile, Error>,
+    /// Workers
+    pub workers: Result<Workers, Error>,
+}
+
+impl PartialDashboard {
+    /// Check if all the endpoints succeeded
+    #[inline]
+    pub fn is_complete(&self) -> bool {
+        self.pool_stats.is_ok() && self.user_profile.is_ok() && self.workers.is_ok()
+    }
+}
+
+impl TryFrom<PartialDashboard> for Dashboard {
+    type Error = Error;
+
+    fn try_from(dashboard: PartialDashboard) -> Result<Self, Self::Error> {
+        Ok(Self {
+            pool_stats: dashboard.pool_stats?,
+            user_profile: dashboard.user_profile?,
+            workers: dashboard.workers?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util;
+
+    #[test]
+    fn test_dashboard_display() {
+        let mut workers: Workers = test_util::workers();
+        workers.workers.get_mut("username.worker2").unwrap().state = WorkerState::Off;
+
+        let dashboard = Dashboard {
+            pool_stats: test_util::pool_stats(),
+            user_profile: test_util::user_profile(),
+            workers,
+        };
+        assert_eq!(
+            dashboard.to_string(),
+            "Pool hashrate: 5.73 Eh/s (5m), 5.52 Eh/s (24h)
+My hashrate: 27.98 Th/s (5m), 28.36 Th/s (24h)
+Balance: 0.15000000 BTC (today: 0.00016667 BTC)
+Workers: 1 ok, 0 low, 1 off, 0 disabled"
+        );
+    }
+}
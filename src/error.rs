@@ -1,31 +1,180 @@
 //! Error
 
+use std::env::VarError;
 use std::fmt;
 
-use reqwest::header::InvalidHeaderValue;
+use reqwest::StatusCode;
+use reqwest::header::{InvalidHeaderName, InvalidHeaderValue};
 
 /// Braiins Pool API Error
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum Error {
     /// Url parse error
     Url(url::ParseError),
     /// Reqwest error
     Reqwest(reqwest::Error),
+    /// Json error
+    Json(serde_json::Error),
+    /// JSON nesting depth exceeds the configured max
+    MaxJsonDepthExceeded(usize),
+    /// Failed to deserialize an endpoint response
+    FailedToDeserialize {
+        /// Endpoint path
+        endpoint: String,
+        /// Json error
+        error: serde_json::Error,
+        /// Excerpt of the response around the error position
+        snippet: Option<String>,
+    },
+    /// Invalid header name
+    InvalidHeaderName(InvalidHeaderName),
     /// Invalid header value
     InvalidHeaderValue(InvalidHeaderValue),
+    /// Environment variable error
+    Env(VarError),
     /// invalid API key
     InvalidApiKey,
+    /// Invalid currency key
+    InvalidCurrency(String),
+    /// Unauthorized (HTTP 401)
+    Unauthorized,
+    /// Not found (HTTP 404)
+    NotFound,
+    /// Conflict (HTTP 409)
+    Conflict,
+    /// Unprocessable entity (HTTP 422)
+    UnprocessableEntity,
+    /// Too many requests (HTTP 429)
+    TooManyRequests,
+    /// Unhandled client error (HTTP 4xx)
+    UnhandledClientError(StatusCode),
+    /// Server error (HTTP 5xx)
+    ServerError(StatusCode),
 }
 
 impl std::error::Error for Error {}
 
+impl Error {
+    /// Get the error kind
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Url(..) => ErrorKind::Url,
+            Self::Reqwest(..) => ErrorKind::Reqwest,
+            Self::Json(..) => ErrorKind::Json,
+            Self::MaxJsonDepthExceeded(..) => ErrorKind::MaxJsonDepthExceeded,
+            Self::FailedToDeserialize { .. } => ErrorKind::FailedToDeserialize,
+            Self::InvalidHeaderName(..) => ErrorKind::InvalidHeaderName,
+            Self::InvalidHeaderValue(..) => ErrorKind::InvalidHeaderValue,
+            Self::Env(..) => ErrorKind::Env,
+            Self::InvalidApiKey => ErrorKind::InvalidApiKey,
+            Self::InvalidCurrency(..) => ErrorKind::InvalidCurrency,
+            Self::Unauthorized => ErrorKind::Unauthorized,
+            Self::NotFound => ErrorKind::NotFound,
+            Self::Conflict => ErrorKind::Conflict,
+            Self::UnprocessableEntity => ErrorKind::UnprocessableEntity,
+            Self::TooManyRequests => ErrorKind::TooManyRequests,
+            Self::UnhandledClientError(..) => ErrorKind::UnhandledClientError,
+            Self::ServerError(..) => ErrorKind::ServerError,
+        }
+    }
+}
+
+/// Error kind
+///
+/// Fieldless counterpart of [`Error`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// Url parse error
+    Url,
+    /// Reqwest error
+    Reqwest,
+    /// Json error
+    Json,
+    /// JSON nesting depth exceeds the configured max
+    MaxJsonDepthExceeded,
+    /// Failed to deserialize an endpoint response
+    FailedToDeserialize,
+    /// Invalid header name
+    InvalidHeaderName,
+    /// Invalid header value
+    InvalidHeaderValue,
+    /// Environment variable error
+    Env,
+    /// invalid API key
+    InvalidApiKey,
+    /// Invalid currency key
+    InvalidCurrency,
+    /// Unauthorized (HTTP 401)
+    Unauthorized,
+    /// Not found (HTTP 404)
+    NotFound,
+    /// Conflict (HTTP 409)
+    Conflict,
+    /// Unprocessable entity (HTTP 422)
+    UnprocessableEntity,
+    /// Too many requests (HTTP 429)
+    TooManyRequests,
+    /// Unhandled client error (HTTP 4xx)
+    UnhandledClientError,
+    /// Server error (HTTP 5xx)
+    ServerError,
+}
+
+impl ErrorKind {
+    /// All the error kinds
+    pub const ALL: [Self; 17] = [
+        Self::Url,
+        Self::Reqwest,
+        Self::Json,
+        Self::MaxJsonDepthExceeded,
+        Self::FailedToDeserialize,
+        Self::InvalidHeaderName,
+        Self::InvalidHeaderValue,
+        Self::Env,
+        Self::InvalidApiKey,
+        Self::InvalidCurrency,
+        Self::Unauthorized,
+        Self::NotFound,
+        Self::Conflict,
+        Self::UnprocessableEntity,
+        Self::TooManyRequests,
+        Self::UnhandledClientError,
+        Self::ServerError,
+    ];
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Url(e) => e.fmt(f),
             Self::Reqwest(e) => e.fmt(f),
+            Self::Json(e) => e.fmt(f),
+            Self::MaxJsonDepthExceeded(max) => write!(f, "JSON nesting depth exceeds {max}"),
+            Self::FailedToDeserialize {
+                endpoint,
+                error,
+                snippet,
+            } => {
+                write!(f, "Failed to deserialize '{endpoint}' response: {error}")?;
+                if let Some(snippet) = snippet {
+                    write!(f, " (near `{snippet}`)")?;
+                }
+                Ok(())
+            }
+            Self::InvalidHeaderName(e) => e.fmt(f),
             Self::InvalidHeaderValue(e) => e.fmt(f),
+            Self::Env(e) => write!(f, "Environment variable error: {e}"),
             Self::InvalidApiKey => f.write_str("Invalid API Key"),
+            Self::InvalidCurrency(key) => write!(f, "Invalid currency key: '{key}'"),
+            Self::Unauthorized => f.write_str("Unauthorized"),
+            Self::NotFound => f.write_str("Not found"),
+            Self::Conflict => f.write_str("Conflict"),
+            Self::UnprocessableEntity => f.write_str("Unprocessable entity"),
+            Self::TooManyRequests => f.write_str("Too many requests"),
+            Self::UnhandledClientError(status) => write!(f, "Unhandled client error: {status}"),
+            Self::ServerError(status) => write!(f, "Server error: {status}"),
         }
     }
 }
@@ -42,8 +191,51 @@ impl From<reqwest::Error> for Error {
     }
 }
 
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Json(e)
+    }
+}
+
+impl From<InvalidHeaderName> for Error {
+    fn from(e: InvalidHeaderName) -> Self {
+        Self::InvalidHeaderName(e)
+    }
+}
+
 impl From<InvalidHeaderValue> for Error {
     fn from(e: InvalidHeaderValue) -> Self {
         Self::InvalidHeaderValue(e)
     }
 }
+
+impl From<VarError> for Error {
+    fn from(e: VarError) -> Self {
+        Self::Env(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_kind() {
+        assert_eq!(Error::InvalidApiKey.kind(), ErrorKind::InvalidApiKey);
+        assert_eq!(Error::NotFound.kind(), ErrorKind::NotFound);
+        assert_eq!(
+            Error::ServerError(StatusCode::BAD_GATEWAY).kind(),
+            Error::ServerError(StatusCode::SERVICE_UNAVAILABLE).kind()
+        );
+        assert_ne!(Error::Unauthorized.kind(), ErrorKind::InvalidApiKey);
+
+        let err = Error::from(url::Url::parse("not a url").unwrap_err());
+        assert_eq!(err.kind(), ErrorKind::Url);
+
+        let err = Error::from(serde_json::from_str::<u64>("abc").unwrap_err());
+        assert_eq!(err.kind(), ErrorKind::Json);
+
+        let err = Error::from(std::env::VarError::NotPresent);
+        assert_eq!(err.kind(), ErrorKind::Env);
+    }
+}
@@ -0,0 +1,83 @@
+//! Export
+
+use std::io::{self, Write};
+
+use serde::Serialize;
+
+use crate::model::Workers;
+
+#[derive(Serialize)]
+struct WorkerSnapshot<'a> {
+    ts: u64,
+    name: &'a str,
+    state: &'a str,
+    last_share: u64,
+    hash_rate_scoring: f64,
+    hash_rate_5m: f64,
+    hash_rate_60m: f64,
+    hash_rate_24h: f64,
+    shares_5m: u64,
+    shares_60m: u64,
+    shares_24h: u64,
+}
+
+/// Append a snapshot of the workers as [JSON Lines](https://jsonlines.org)
+///
+/// Writes one JSON object per worker per line, tagged with the `ts` timestamp and the worker `name`.
+/// Hash rates are expressed in **hashes/sec**.
+pub fn append_worker_snapshot_jsonl<W>(workers: &Workers, ts: u64, w: &mut W) -> io::Result<()>
+where
+    W: Write,
+{
+    for (name, worker) in workers.workers.iter() {
+        let snapshot = WorkerSnapshot {
+            ts,
+            name,
+            state: worker.state.as_str(),
+            last_share: worker.last_share,
+            hash_rate_scoring: worker.hash_rate_scoring.to_hashes(),
+            hash_rate_5m: worker.hash_rate_5m.to_hashes(),
+            hash_rate_60m: worker.hash_rate_60m.to_hashes(),
+            hash_rate_24h: worker.hash_rate_24h.to_hashes(),
+            shares_5m: worker.shares_5m,
+            shares_60m: worker.shares_60m,
+            shares_24h: worker.shares_24h,
+        };
+
+        serde_json::to_writer(&mut *w, &snapshot)?;
+        w.write_all(b"\n")?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::*;
+    use crate::test_util;
+
+    #[test]
+    fn test_append_worker_snapshot_jsonl() {
+        let workers: Workers = test_util::workers();
+
+        let mut buf: Vec<u8> = Vec::new();
+        append_worker_snapshot_jsonl(&workers, 1542103300, &mut buf).unwrap();
+        append_worker_snapshot_jsonl(&workers, 1542103600, &mut buf).unwrap();
+
+        let output = String::from_utf8(buf).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), workers.workers.len() * 2);
+
+        for line in lines {
+            let value: Value = serde_json::from_str(line).unwrap();
+            let name: &str = value["name"].as_str().unwrap();
+            assert!(workers.workers.contains_key(name));
+            assert!(value["ts"].as_u64().is_some());
+        }
+
+        let first: Value = serde_json::from_str(output.lines().next().unwrap()).unwrap();
+        assert_eq!(first["ts"], 1542103300);
+    }
+}
@@ -0,0 +1,70 @@
+//! Fixtures
+//!
+//! Canned responses, to test downstream apps without network I/O.
+
+use crate::client::BraiinsPoolClient;
+use crate::model::{DailyReward, DailyRewards, PoolStats, UserProfile, Workers};
+
+/// Canned endpoint responses
+///
+/// Endpoints without a fixture fail with [`Error::NotFound`](crate::error::Error::NotFound).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FixtureSet {
+    /// Pool stats
+    pub pool_stats: Option<PoolStats>,
+    /// User profile
+    pub user_profile: Option<UserProfile>,
+    /// Workers
+    pub workers: Option<Workers>,
+    /// Daily rewards
+    pub daily_rewards: Option<Vec<DailyReward>>,
+}
+
+impl FixtureSet {
+    /// Construct an empty fixture set
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set pool stats
+    #[inline]
+    pub fn pool_stats(mut self, pool_stats: PoolStats) -> Self {
+        self.pool_stats = Some(pool_stats);
+        self
+    }
+
+    /// Set user profile
+    #[inline]
+    pub fn user_profile(mut self, user_profile: UserProfile) -> Self {
+        self.user_profile = Some(user_profile);
+        self
+    }
+
+    /// Set workers
+    #[inline]
+    pub fn workers(mut self, workers: Workers) -> Self {
+        self.workers = Some(workers);
+        self
+    }
+
+    /// Set daily rewards
+    #[inline]
+    pub fn daily_rewards(mut self, daily_rewards: Vec<DailyReward>) -> Self {
+        self.daily_rewards = Some(daily_rewards);
+        self
+    }
+
+    pub(crate) fn get_daily_rewards(&self) -> Option<DailyRewards> {
+        self.daily_rewards
+            .clone()
+            .map(|daily_rewards| DailyRewards { daily_rewards })
+    }
+}
+
+impl From<FixtureSet> for BraiinsPoolClient {
+    #[inline]
+    fn from(fixtures: FixtureSet) -> Self {
+        Self::from_fixtures(fixtures)
+    }
+}
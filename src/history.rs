@@ -0,0 +1,120 @@
+//! Hashrate history
+//!
+//! The pool API only exposes point-in-time hash rates,
+//! so the history is recorded client-side from successive [`PoolStats`].
+
+use std::collections::VecDeque;
+
+use crate::model::{HashratePoint, PoolStats};
+
+/// Pool hashrate history recorder
+///
+/// Ring buffer keeping the latest `capacity` points.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HashrateHistory {
+    capacity: usize,
+    points: VecDeque<HashratePoint>,
+}
+
+impl HashrateHistory {
+    /// Construct a new recorder keeping up to `capacity` points
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            points: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Record the pool hash rate for the last 5 minutes, timestamped with [`PoolStats::update_ts`]
+    ///
+    /// Stats not newer than the latest recorded point are skipped,
+    /// so polling more often than the pool updates doesn't produce duplicates.
+    pub fn record(&mut self, stats: &PoolStats) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if self
+            .points
+            .back()
+            .is_some_and(|latest| stats.update_ts <= latest.timestamp)
+        {
+            return;
+        }
+
+        if self.points.len() == self.capacity {
+            self.points.pop_front();
+        }
+
+        self.points.push_back(HashratePoint {
+            timestamp: stats.update_ts,
+            hash_rate: stats.pool_5m_hash_rate,
+        });
+    }
+
+    /// Get recorded points (oldest first)
+    #[inline]
+    pub fn points(&self) -> impl Iterator<Item = &HashratePoint> {
+        self.points.iter()
+    }
+
+    /// Get latest recorded point
+    #[inline]
+    pub fn latest(&self) -> Option<&HashratePoint> {
+        self.points.back()
+    }
+
+    /// Get number of recorded points
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Check if no points have been recorded
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{HashRate, HashRateUnit};
+    use crate::test_util;
+
+    fn pool_stats(update_ts: u64, hash_rate: f64) -> PoolStats {
+        let mut stats: PoolStats = test_util::pool_stats();
+        stats.update_ts = update_ts;
+        stats.pool_5m_hash_rate = HashRate::new(HashRateUnit::GH, hash_rate);
+        stats
+    }
+
+    #[test]
+    fn test_hashrate_history_record() {
+        let mut history = HashrateHistory::new(2);
+        assert!(history.is_empty());
+
+        history.record(&pool_stats(1699938000, 5700000000.0));
+        history.record(&pool_stats(1699938300, 5800000000.0));
+
+        // Duplicated update
+        history.record(&pool_stats(1699938300, 5800000000.0));
+        assert_eq!(history.len(), 2);
+
+        // Oldest point is evicted
+        history.record(&pool_stats(1699938600, 5900000000.0));
+        let timestamps: Vec<u64> = history.points().map(|p| p.timestamp).collect();
+        assert_eq!(timestamps, vec![1699938300, 1699938600]);
+
+        let latest = history.latest().unwrap();
+        assert_eq!(latest.hash_rate.value(), 5900000000.0);
+    }
+
+    #[test]
+    fn test_hashrate_history_zero_capacity() {
+        let mut history = HashrateHistory::new(0);
+        history.record(&pool_stats(1699938000, 5700000000.0));
+        assert!(history.is_empty());
+    }
+}
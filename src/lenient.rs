@@ -0,0 +1,337 @@
+//! Lenient deserialization
+//!
+//! Keeps the typed models usable when the API drifts (i.e. a field switching between number and string):
+//! instead of failing the whole response, the offending fields are repaired and reported as [`FieldWarning`]s.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Number, Value};
+use serde_path_to_error::{Path, Segment};
+
+/// Upper bound of the fields repaired in a single response
+const MAX_REPAIRS: usize = 64;
+
+/// Response deserialized in lenient mode
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartialResponse<T> {
+    /// Deserialized value
+    pub value: T,
+    /// Fields that had to be repaired (empty if the response matched the model)
+    pub warnings: Vec<FieldWarning>,
+}
+
+impl<T> PartialResponse<T> {
+    /// Wrap a value without warnings
+    #[inline]
+    pub fn complete(value: T) -> Self {
+        Self {
+            value,
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Check if the response matched the model (no warnings)
+    #[inline]
+    pub fn is_complete(&self) -> bool {
+        self.warnings.is_empty()
+    }
+}
+
+/// Field-level deserialization error, repaired in lenient mode
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FieldWarning {
+    /// Dot-separated path of the field (i.e. `blocks.549753.value`)
+    pub path: String,
+    /// Original error
+    pub message: String,
+}
+
+impl fmt::Display for FieldWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+/// Deserialize a value leniently
+///
+/// On a field error, the value is coerced between number and string or, failing that,
+/// replaced by an empty value of a matching type (`0`, `""`, `false`, `[]`, `{}` or `null`).
+/// Missing fields are filled the same way.
+///
+/// Fails with the first unrepairable error.
+pub fn from_value_lenient<T>(mut value: Value) -> Result<PartialResponse<T>, serde_json::Error>
+where
+    T: DeserializeOwned,
+{
+    let mut warnings: Vec<FieldWarning> = Vec::new();
+    let mut repaired: HashSet<String> = HashSet::new();
+
+    loop {
+        let error = match serde_path_to_error::deserialize::<_, T>(&value) {
+            Ok(value) => return Ok(PartialResponse { value, warnings }),
+            Err(error) => error,
+        };
+
+        if warnings.len() >= MAX_REPAIRS {
+            return Err(error.into_inner());
+        }
+
+        let location: Location = Location::new(error.path(), error.inner());
+
+        // Repair each field once, to always terminate
+        if !repaired.insert(location.path.clone()) {
+            return Err(error.into_inner());
+        }
+
+        if !location.repair::<T>(&mut value) {
+            return Err(error.into_inner());
+        }
+
+        warnings.push(FieldWarning {
+            path: location.path,
+            message: error.into_inner().to_string(),
+        });
+    }
+}
+
+/// Location of a field error
+struct Location {
+    segments: Vec<Segment>,
+    /// Name of the field, if missing
+    missing: Option<String>,
+    /// Dot-separated path of the field
+    path: String,
+}
+
+impl Location {
+    fn new(path: &Path, error: &serde_json::Error) -> Self {
+        let segments: Vec<Segment> = path.iter().cloned().collect();
+        let missing: Option<String> = error
+            .to_string()
+            .strip_prefix("missing field `")
+            .and_then(|name| name.split_once('`'))
+            .map(|(name, _)| name.to_string());
+
+        let mut parts: Vec<String> = segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Seq { index } => index.to_string(),
+                Segment::Map { key } => key.clone(),
+                Segment::Enum { variant } => variant.clone(),
+                Segment::Unknown => String::from("?"),
+            })
+            .collect();
+        parts.extend(missing.clone());
+
+        Self {
+            segments,
+            missing,
+            path: parts.join("."),
+        }
+    }
+
+    /// Try the candidates of the field, keeping the first one that fixes its error
+    fn repair<T>(&self, root: &mut Value) -> bool
+    where
+        T: DeserializeOwned,
+    {
+        let candidates: Vec<Value> = match self.missing {
+            Some(..) => defaults(),
+            None => match get(root, &self.segments) {
+                Some(value) => coercions(value).into_iter().chain(defaults()).collect(),
+                None => return false,
+            },
+        };
+
+        for candidate in candidates {
+            let mut attempt: Value = root.clone();
+
+            let target: Option<&mut Value> =
+                match (&self.missing, get_mut(&mut attempt, &self.segments)) {
+                    (Some(name), Some(Value::Object(map))) => {
+                        Some(map.entry(name.clone()).or_insert(Value::Null))
+                    }
+                    (Some(..), _) => None,
+                    (None, value) => value,
+                };
+
+            match target {
+                Some(value) => *value = candidate,
+                None => return false,
+            }
+
+            // Fixed if the error moved elsewhere (or disappeared)
+            let fixed: bool = match serde_path_to_error::deserialize::<_, T>(&attempt) {
+                Ok(..) => true,
+                Err(error) => Location::new(error.path(), error.inner()).path != self.path,
+            };
+
+            if fixed {
+                *root = attempt;
+                return true;
+            }
+        }
+
+        false
+    }
+}
+
+fn get<'a>(mut value: &'a Value, segments: &[Segment]) -> Option<&'a Value> {
+    for segment in segments {
+        value = match (segment, value) {
+            (Segment::Map { key }, Value::Object(map)) => map.get(key)?,
+            (Segment::Seq { index }, Value::Array(array)) => array.get(*index)?,
+            _ => return None,
+        };
+    }
+    Some(value)
+}
+
+fn get_mut<'a>(mut value: &'a mut Value, segments: &[Segment]) -> Option<&'a mut Value> {
+    for segment in segments {
+        value = match (segment, value) {
+            (Segment::Map { key }, Value::Object(map)) => map.get_mut(key)?,
+            (Segment::Seq { index }, Value::Array(array)) => array.get_mut(*index)?,
+            _ => return None,
+        };
+    }
+    Some(value)
+}
+
+/// Same value, with the other representation
+fn coercions(value: &Value) -> Vec<Value> {
+    match value {
+        Value::String(s) => {
+            let s: &str = s.trim();
+            let mut candidates: Vec<Value> = Vec::new();
+            if let Ok(n) = s.parse::<u64>() {
+                candidates.push(Value::from(n));
+            }
+            if let Some(n) = s.parse::<f64>().ok().and_then(Number::from_f64) {
+                candidates.push(Value::Number(n));
+            }
+            if let Ok(b) = s.parse::<bool>() {
+                candidates.push(Value::Bool(b));
+            }
+            candidates
+        }
+        Value::Number(n) => {
+            let mut candidates: Vec<Value> = vec![Value::String(n.to_string())];
+            // i.e. a float where an integer is expected
+            if let Some(n) = n.as_f64().filter(|n| n.is_finite() && *n >= 0.0) {
+                candidates.push(Value::from(n.trunc() as u64));
+            }
+            candidates
+        }
+        Value::Bool(b) => vec![Value::String(b.to_string())],
+        _ => Vec::new(),
+    }
+}
+
+/// Empty values
+fn defaults() -> Vec<Value> {
+    vec![
+        Value::from(0),
+        Value::String(String::new()),
+        Value::Bool(false),
+        Value::Array(Vec::new()),
+        Value::Object(Map::new()),
+        Value::Null,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+    use serde_json::json;
+
+    use super::*;
+    use crate::model::{HashRate, HashRateUnit, UserProfile, WorkerState, Workers};
+    use crate::test_util;
+
+    #[test]
+    fn test_lenient_complete() {
+        let res: PartialResponse<Vec<u64>> = from_value_lenient(json!([1, 2])).unwrap();
+        assert_eq!(res, PartialResponse::complete(vec![1, 2]));
+        assert!(res.is_complete());
+    }
+
+    #[test]
+    fn test_lenient_coercion_and_missing_field() {
+        // `hash_rate_5m` is garbage, `low_workers` became a string and `hash_rate_24h` is missing
+        let mut value: Value = test_util::patched(
+            test_util::USER_PROFILE,
+            json!({"hash_rate_5m": {"value": 27978}, "low_workers": "1"}),
+        );
+        value.as_object_mut().unwrap().remove("hash_rate_24h");
+        let res: PartialResponse<UserProfile> = from_value_lenient(value).unwrap();
+        assert_eq!(res.value.hash_rate_5m, HashRate::new(HashRateUnit::GH, 0.0));
+        assert_eq!(
+            res.value.hash_rate_60m,
+            HashRate::new(HashRateUnit::GH, 28191.0)
+        );
+        assert_eq!(
+            res.value.hash_rate_24h,
+            HashRate::new(HashRateUnit::GH, 0.0)
+        );
+        assert_eq!(res.value.low_workers, 1);
+        assert_eq!(res.value.ok_workers, 2);
+
+        let mut paths: Vec<&str> = res.warnings.iter().map(|w| w.path.as_str()).collect();
+        paths.sort();
+        assert_eq!(paths, vec!["hash_rate_24h", "hash_rate_5m", "low_workers"]);
+
+        let warning: &FieldWarning = res
+            .warnings
+            .iter()
+            .find(|w| w.path == "hash_rate_24h")
+            .unwrap();
+        assert_eq!(warning.message, "missing field `hash_rate_24h`");
+        assert_eq!(
+            warning.to_string(),
+            "hash_rate_24h: missing field `hash_rate_24h`"
+        );
+    }
+
+    #[test]
+    fn test_lenient_nested_path() {
+        let value: Value = json!({
+            "workers": {
+                "username.worker1": {
+                    "state": "ok",
+                    "last_share": "1542103204",
+                    "hash_rate_unit": "Gh/s",
+                    "hash_rate_scoring": 15342,
+                    "hash_rate_5m": 14977,
+                    "hash_rate_60m": 15302,
+                    "hash_rate_24h": 15351
+                }
+            }
+        });
+        let res: PartialResponse<Workers> = from_value_lenient(value).unwrap();
+        let worker = &res.value.workers["username.worker1"];
+        assert_eq!(worker.state, WorkerState::Ok);
+        assert_eq!(worker.last_share, 1542103204);
+        assert_eq!(res.warnings.len(), 1);
+        assert_eq!(res.warnings[0].path, "workers.username.worker1.last_share");
+    }
+
+    #[test]
+    fn test_lenient_unrepairable() {
+        #[derive(Debug, Deserialize)]
+        enum Kind {
+            A,
+        }
+
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Item {
+            kind: Kind,
+        }
+
+        let res = from_value_lenient::<Item>(json!({"kind": "B"}));
+        assert!(res.is_err());
+    }
+}
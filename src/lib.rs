@@ -4,9 +4,25 @@
 #![warn(rustdoc::bare_urls)]
 #![doc = include_str!("../README.md")]
 
+pub mod api;
+pub mod api_key;
 pub mod builder;
+pub mod calc;
 pub mod client;
+pub mod dashboard;
 pub mod error;
+pub mod export;
+#[cfg(feature = "fixtures")]
+pub mod fixtures;
+pub mod history;
+pub mod lenient;
+pub mod meta;
 pub mod model;
+pub mod poll;
 pub mod prelude;
+mod rate_limit;
+pub mod rewards;
+pub mod stats;
+#[cfg(test)]
+mod test_util;
 mod util;
@@ -0,0 +1,61 @@
+//! Response metadata
+
+use reqwest::StatusCode;
+use reqwest::header::HeaderMap;
+
+/// Rate limit budget header
+const RATE_LIMIT_REMAINING: &str = "X-RateLimit-Remaining";
+
+/// Metadata of a successful response
+///
+/// Returned by the `*_with_meta` methods of [`BraiinsPoolClient`](crate::client::BraiinsPoolClient),
+/// i.e. to pace polling on the rate limit headers.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResponseMeta {
+    /// HTTP status
+    pub status: StatusCode,
+    /// Response headers
+    pub headers: HeaderMap,
+}
+
+impl ResponseMeta {
+    /// Get a header value, if present and valid UTF-8 (case-insensitive name)
+    #[inline]
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name)?.to_str().ok()
+    }
+
+    /// Requests left in the current rate limit window (`X-RateLimit-Remaining` header)
+    ///
+    /// `None` if the header is missing or invalid.
+    #[inline]
+    pub fn rate_limit_remaining(&self) -> Option<u64> {
+        self.header(RATE_LIMIT_REMAINING)?.trim().parse().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::HeaderValue;
+
+    use super::*;
+
+    #[test]
+    fn test_response_meta_headers() {
+        let mut meta = ResponseMeta::default();
+        assert_eq!(meta.status, StatusCode::OK);
+        assert_eq!(meta.rate_limit_remaining(), None);
+
+        meta.headers
+            .insert("x-ratelimit-remaining", HeaderValue::from_static("42"));
+        meta.headers
+            .insert("x-custom", HeaderValue::from_static("value"));
+        assert_eq!(meta.rate_limit_remaining(), Some(42));
+        assert_eq!(meta.header("X-Custom"), Some("value"));
+        assert_eq!(meta.header("x-missing"), None);
+
+        meta.headers
+            .insert("x-ratelimit-remaining", HeaderValue::from_static("many"));
+        assert_eq!(meta.rate_limit_remaining(), None);
+    }
+}
@@ -1,20 +1,222 @@
 //! Models
 
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap, hash_map};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::iter::Sum;
+use std::marker::PhantomData;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use serde::{Deserialize, Deserializer};
+use serde::de::{self, IgnoredAny, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(feature = "extra-fields")]
+use serde_json::Value;
+#[cfg(feature = "time")]
+use time::OffsetDateTime;
 
-use crate::util::deserialize_number_from_string;
+use crate::error::Error;
+#[cfg(feature = "time")]
+use crate::util;
+use crate::util::{
+    deserialize_borrowed_map, deserialize_number_from_string, deserialize_number_with_unit,
+};
 
-#[derive(Deserialize)]
-pub(crate) struct BtcResponse<T> {
-    pub btc: T,
+/// Currency
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Currency {
+    /// Bitcoin
+    #[default]
+    Btc,
+    /// Currency not enumerated by this crate (i.e. `ltc`)
+    ///
+    /// Parse it with [`FromStr`], i.e. `"ltc".parse::<Currency>()`.
+    Other(CurrencyKey),
+}
+
+impl Currency {
+    /// Map a known key to its currency
+    ///
+    /// Single source of the currency keys: used by [`FromStr`] and [`CurrencyResponse`].
+    fn from_known_key(key: &str) -> Option<Self> {
+        match key {
+            "btc" => Some(Self::Btc),
+            _ => None,
+        }
+    }
+
+    /// Get the key of the currency
+    fn key(&self) -> &str {
+        match self {
+            Self::Btc => "btc",
+            Self::Other(key) => key.as_str(),
+        }
+    }
+
+    /// Get the path segment of the endpoints (i.e. `btc` in `/stats/json/btc`)
+    #[inline]
+    pub fn as_path_segment(&self) -> &str {
+        self.key()
+    }
+
+    /// Get the key wrapping the responses (i.e. `btc`)
+    #[inline]
+    pub fn response_key(&self) -> &str {
+        self.key()
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.key())
+    }
+}
+
+impl FromStr for Currency {
+    type Err = Error;
+
+    /// Parse a currency key, case-insensitively
+    ///
+    /// Keys not enumerated by this crate are parsed as [`Currency::Other`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() || !s.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(Error::InvalidCurrency(s.to_string()));
+        }
+
+        let key: String = s.to_ascii_lowercase();
+        Ok(Self::from_known_key(&key).unwrap_or(Self::Other(CurrencyKey(key))))
+    }
+}
+
+/// Key of a [`Currency::Other`]
+///
+/// Lowercase, ASCII alphanumeric and not a known currency: only built by [`Currency::from_str`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CurrencyKey(String);
+
+impl CurrencyKey {
+    /// Get the key as string slice
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for CurrencyKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Response wrapped by currency key (i.e. `{ "btc": T }`)
+///
+/// Useful to deserialize full API responses captured elsewhere (i.e. from logs).
+/// Keys that aren't a known currency (i.e. `username`) are ignored:
+/// use [`CurrencyResponse::deserialize_for`] to unwrap a [`Currency::Other`] response.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurrencyResponse<T> {
+    /// Currency
+    pub currency: Currency,
+    /// Response data
+    pub data: T,
+}
+
+impl<T> CurrencyResponse<T> {
+    /// Deserialize a response wrapped by the [`response_key`](Currency::response_key) of `currency`
+    ///
+    /// The other keys are ignored.
+    pub fn deserialize_for<'de, D>(deserializer: D, currency: &Currency) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: Deserialize<'de>,
+    {
+        deserializer.deserialize_map(ResponseVisitor {
+            expected: Some(currency),
+            marker: PhantomData,
+        })
+    }
+}
+
+impl<'de, T> Deserialize<'de> for CurrencyResponse<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(ResponseVisitor {
+            expected: None,
+            marker: PhantomData,
+        })
+    }
+}
+
+struct ResponseVisitor<'a, T> {
+    /// Expected currency (any known one if `None`)
+    expected: Option<&'a Currency>,
+    marker: PhantomData<T>,
+}
+
+impl<'de, T> Visitor<'de> for ResponseVisitor<'_, T>
+where
+    T: Deserialize<'de>,
+{
+    type Value = CurrencyResponse<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a currency-keyed response")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut response: Option<CurrencyResponse<T>> = None;
+
+        while let Some(key) = map.next_key::<String>()? {
+            let currency: Option<Currency> = match (&response, self.expected) {
+                (Some(..), _) => None,
+                (None, Some(expected)) => {
+                    (key == expected.response_key()).then(|| expected.clone())
+                }
+                (None, None) => Currency::from_known_key(&key),
+            };
+
+            match currency {
+                Some(currency) => {
+                    let data: T = map.next_value()?;
+                    response = Some(CurrencyResponse { currency, data });
+                }
+                None => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+
+        response.ok_or_else(|| match self.expected {
+            Some(expected) => de::Error::custom(format!(
+                "missing currency key `{}`",
+                expected.response_key()
+            )),
+            None => de::Error::custom("missing currency key"),
+        })
+    }
 }
 
 /// Hash rate unit
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+///
+/// Serialized with its canonical string (i.e. `Gh/s`), so it can be used as a map key.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[non_exhaustive]
 pub enum HashRateUnit {
     /// Hash per second (1 hash/sec)
+    #[default]
     #[serde(rename = "H/s", alias = "h/s")]
     H,
     /// Kilohash per second (1,000 hashes/sec)
@@ -44,9 +246,47 @@ pub enum HashRateUnit {
 }
 
 impl HashRateUnit {
+    /// Units, from the smallest to the largest
+    const ALL: [Self; 9] = [
+        Self::H,
+        Self::KH,
+        Self::MH,
+        Self::GH,
+        Self::TH,
+        Self::PH,
+        Self::EH,
+        Self::ZH,
+        Self::YH,
+    ];
+
+    /// Get unit as string (i.e. `Gh/s`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::H => "H/s",
+            Self::KH => "Kh/s",
+            Self::MH => "Mh/s",
+            Self::GH => "Gh/s",
+            Self::TH => "Th/s",
+            Self::PH => "Ph/s",
+            Self::EH => "Eh/s",
+            Self::ZH => "Zh/s",
+            Self::YH => "Yh/s",
+        }
+    }
+
+    /// Get the largest unit that keeps `hashes` (**hashes/sec**, sign ignored) at least 1
+    fn largest_for(hashes: f64) -> Self {
+        let abs: f64 = hashes.abs();
+        Self::ALL
+            .into_iter()
+            .rev()
+            .find(|unit| abs >= 10f64.powi(unit.exponent()))
+            .unwrap_or(Self::H)
+    }
+
     fn exponent(&self) -> i32 {
         match self {
-            Self::H => 1,
+            Self::H => 0,
             Self::KH => 3,
             Self::MH => 6,
             Self::GH => 9,
@@ -60,15 +300,24 @@ impl HashRateUnit {
 }
 
 /// Hashrate
-#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+///
+/// Equality, ordering and hashing compare the hashrate as **hashes/sec** (see [`HashRate::to_hashes`]),
+/// regardless of the unit, using [`f64::total_cmp`]:
+/// `-0.0` is less than `0.0` and `NaN` values are ordered after (positive) or before (negative) all the other values,
+/// so the type can be used as a [`BTreeMap`] key.
+///
+/// The comparison is exact: conversions between units can introduce float rounding errors,
+/// so use [`HashRate::approx_eq`] to compare computed hashrates.
+#[derive(Debug, Clone, Copy, Default)]
 pub struct HashRate {
     unit: HashRateUnit,
     value: f64,
 }
 
 impl HashRate {
+    /// Construct a new hashrate
     #[inline]
-    fn new(unit: HashRateUnit, value: f64) -> Self {
+    pub fn new(unit: HashRateUnit, value: f64) -> Self {
         Self { unit, value }
     }
 
@@ -85,15 +334,241 @@ impl HashRate {
     }
 
     /// Get hashrate as **hashes/sec**.
+    ///
+    /// Overflows to `inf` when the value exceeds [`f64::MAX`] hashes/sec (i.e. above ~1.8 × 10^284 Yh/s):
+    /// use [`HashRate::try_to_hashes`] to detect it.
     #[inline]
     pub fn to_hashes(&self) -> f64 {
         self.value * 10f64.powi(self.unit.exponent())
     }
+
+    /// Get hashrate as **hashes/sec**, if finite
+    ///
+    /// Returns `None` on overflow or if the value is already non-finite (`inf` or `NaN`).
+    #[inline]
+    pub fn try_to_hashes(&self) -> Option<f64> {
+        let hashes: f64 = self.to_hashes();
+        hashes.is_finite().then_some(hashes)
+    }
+
+    /// Check if the hashrates are equal within `epsilon` **hashes/sec**, regardless of the unit
+    #[inline]
+    pub fn approx_eq(&self, other: &Self, epsilon: f64) -> bool {
+        (self.to_hashes() - other.to_hashes()).abs() <= epsilon
+    }
+
+    /// Check if the hashrate is zero (i.e. offline workers)
+    ///
+    /// Exact comparison, without epsilon: `-0.0` is zero, while any tiny positive value is not.
+    #[inline]
+    pub fn is_zero(&self) -> bool {
+        self.to_hashes() == 0.0
+    }
+
+    /// Round up to a "nice" value (1, 2 or 5 × 10^n **hashes/sec**), expressed in the same unit
+    ///
+    /// Useful for chart axis bounds. Zero, negative and non-finite hashrates are returned unchanged.
+    pub fn nice_ceiling(&self) -> Self {
+        let hashes: f64 = self.to_hashes();
+
+        if hashes <= 0.0 || !hashes.is_finite() {
+            return *self;
+        }
+
+        let magnitude: f64 = 10f64.powi(hashes.log10().floor() as i32);
+        let fraction: f64 = hashes / magnitude;
+        let nice: f64 = if fraction <= 1.0 {
+            1.0
+        } else if fraction <= 2.0 {
+            2.0
+        } else if fraction <= 5.0 {
+            5.0
+        } else {
+            10.0
+        };
+
+        Self::new(HashRateUnit::H, nice * magnitude).convert(self.unit)
+    }
+
+    /// Convert hashrate to another [`HashRateUnit`].
+    #[inline]
+    pub fn convert(&self, unit: HashRateUnit) -> Self {
+        Self::new(unit, self.to_hashes() / 10f64.powi(unit.exponent()))
+    }
+
+    /// Convert to the largest [`HashRateUnit`] that keeps the value at least 1 (i.e. `5727000000 Gh/s` to `5.727 Eh/s`)
+    #[inline]
+    pub fn scaled(&self) -> Self {
+        self.convert(HashRateUnit::largest_for(self.to_hashes()))
+    }
+
+    /// Get the change from `other` to `self`
+    ///
+    /// Negative if `self` is lower than `other` (i.e. a hashrate drop).
+    #[inline]
+    pub fn delta(&self, other: &Self) -> HashRateDelta {
+        HashRateDelta::from_hashes(self.to_hashes() - other.to_hashes())
+    }
+}
+
+impl fmt::Display for HashRate {
+    /// Value and unit (i.e. `28.36 Th/s`), honoring the precision if specified
+    ///
+    /// Displayed in its own unit: use [`HashRate::scaled`] first to auto-scale it.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match f.precision() {
+            Some(precision) => write!(f, "{:.precision$} {}", self.value, self.unit.as_str()),
+            None => write!(f, "{} {}", self.value, self.unit.as_str()),
+        }
+    }
+}
+
+impl PartialEq for HashRate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for HashRate {}
+
+impl PartialOrd for HashRate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for HashRate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.to_hashes().total_cmp(&other.to_hashes())
+    }
+}
+
+impl Hash for HashRate {
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        self.to_hashes().to_bits().hash(state);
+    }
+}
+
+impl Sum for HashRate {
+    /// Sum hashrates, expressed in the unit of the first one (`H/s` if empty).
+    fn sum<I>(iter: I) -> Self
+    where
+        I: Iterator<Item = Self>,
+    {
+        let mut unit: Option<HashRateUnit> = None;
+        let mut hashes: f64 = 0.0;
+
+        for hash_rate in iter {
+            unit.get_or_insert(hash_rate.unit);
+            hashes += hash_rate.to_hashes();
+        }
+
+        Self::new(HashRateUnit::H, hashes).convert(unit.unwrap_or(HashRateUnit::H))
+    }
+}
+
+/// Hashrate change, in **hashes/sec**
+///
+/// Unlike [`HashRate`], it can be negative.
+/// Displayed in the largest unit that keeps the value at least 1 (i.e. `-1.2 Th/s`),
+/// with 1 decimal unless a precision is specified.
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+pub struct HashRateDelta {
+    hashes: f64,
+}
+
+impl HashRateDelta {
+    /// Construct from **hashes/sec**
+    #[inline]
+    pub fn from_hashes(hashes: f64) -> Self {
+        Self { hashes }
+    }
+
+    /// Get the change as **hashes/sec**
+    #[inline]
+    pub fn to_hashes(&self) -> f64 {
+        self.hashes
+    }
+
+    /// Check if it's a drop
+    #[inline]
+    pub fn is_negative(&self) -> bool {
+        self.hashes < 0.0
+    }
+}
+
+impl fmt::Display for HashRateDelta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let unit: HashRateUnit = HashRateUnit::largest_for(self.hashes);
+        let value: f64 = self.hashes / 10f64.powi(unit.exponent());
+        let precision: usize = f.precision().unwrap_or(1);
+        write!(f, "{value:.precision$} {}", unit.as_str())
+    }
+}
+
+/// Block state
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum BlockState {
+    /// Block is confirmed
+    Confirmed,
+    /// Block is waiting for confirmations
+    Pending,
+    /// Block has been orphaned
+    Orphaned,
+    /// Unknown state
+    #[default]
+    Unknown,
+}
+
+impl BlockState {
+    /// Get state as string (`confirmed`/`pending`/`orphaned`/`unknown`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Confirmed => "confirmed",
+            Self::Pending => "pending",
+            Self::Orphaned => "orphaned",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+impl Serialize for BlockState {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for BlockState {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let state: String = String::deserialize(deserializer)?;
+        Ok(match state.as_str() {
+            "confirmed" => Self::Confirmed,
+            "new" | "pending" => Self::Pending,
+            "orphan" | "orphaned" => Self::Orphaned,
+            _ => Self::Unknown,
+        })
+    }
 }
 
 /// Block
-#[derive(Debug, Clone, PartialEq, PartialOrd, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, PartialOrd, Deserialize)]
+#[non_exhaustive]
 pub struct Block {
+    /// Block height
+    ///
+    /// Parsed from the `blocks` map key when deserialized as part of [`PoolStats`].
+    #[serde(skip)]
+    pub height: Option<u64>,
     /// Unix time when given block was found
     pub date_found: u64,
     /// Duration of the round leading to given block
@@ -101,8 +576,9 @@ pub struct Block {
     /// Number of shares collected during the round
     pub total_shares: u64,
     /// State of given block
-    pub state: String,
-    /// Number of confirmations left
+    pub state: BlockState,
+    /// Number of confirmations left (`0` if omitted, i.e. for confirmed blocks)
+    #[serde(default)]
     pub confirmations_left: u32,
     /// Block value
     #[serde(deserialize_with = "deserialize_number_from_string")]
@@ -111,12 +587,88 @@ pub struct Block {
     #[serde(deserialize_with = "deserialize_number_from_string")]
     pub user_reward: f64,
     /// Pool scoring hash rate at the time when block was found
-    pub pool_scoring_hash_rate: f64,
+    ///
+    /// In the `hash_rate_unit` of the enclosing [`PoolStats`] when deserialized as part of it,
+    /// in hashes/sec otherwise (the block itself carries no unit).
+    #[serde(deserialize_with = "deserialize_hash_rate_from_hashes")]
+    pub pool_scoring_hash_rate: HashRate,
+}
+
+/// Deserialize a bare number of **hashes/sec** as [`HashRate`]
+fn deserialize_hash_rate_from_hashes<'de, D>(deserializer: D) -> Result<HashRate, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let hashes: f64 = deserialize_number_from_string(deserializer)?;
+    Ok(HashRate::new(HashRateUnit::H, hashes))
+}
+
+impl Block {
+    /// Check if the block is confirmed (`confirmed` state and no confirmations left)
+    #[inline]
+    pub fn is_confirmed(&self) -> bool {
+        self.state == BlockState::Confirmed && self.confirmations_left == 0
+    }
+
+    /// Confirmations left before the block is confirmed
+    ///
+    /// Returns `None` if the block is already [confirmed](Block::is_confirmed) or orphaned (it'll never be).
+    #[inline]
+    pub fn confirmations_remaining(&self) -> Option<u32> {
+        if self.is_confirmed() || self.state == BlockState::Orphaned {
+            return None;
+        }
+
+        Some(self.confirmations_left)
+    }
+
+    /// Luck of the round leading to the block, as a percentage
+    ///
+    /// Computed as `expected hashes / actual hashes * 100`, where:
+    /// * `expected hashes = network_difficulty * 2^32`
+    /// * `actual hashes = pool_scoring_hash_rate (hashes/sec) * mining_duration`
+    ///
+    /// Above `100.0` if the block was found faster than expected.
+    /// `network_difficulty` is the difficulty at the time the block was found
+    /// (see [`PoolStats::network_difficulty`] for the current one).
+    /// [`total_shares`](Block::total_shares) isn't used, since the share difficulty isn't reported.
+    ///
+    /// Returns `0.0` if the actual hashes are zero.
+    pub fn luck(&self, network_difficulty: f64) -> f64 {
+        let actual: f64 = self.pool_scoring_hash_rate.to_hashes() * self.mining_duration as f64;
+
+        if actual <= 0.0 {
+            return 0.0;
+        }
+
+        network_difficulty * 2f64.powi(32) / actual * 100.0
+    }
+
+    /// Estimate the time left until the block is confirmed
+    ///
+    /// Computed as `confirmations_left * avg_block_interval`
+    /// (see [`PoolStats::average_block_interval`]).
+    #[inline]
+    pub fn estimated_confirmation_time(&self, avg_block_interval: Duration) -> Duration {
+        avg_block_interval.saturating_mul(self.confirmations_left)
+    }
+
+    /// Get [`date_found`](Block::date_found) as UTC [`OffsetDateTime`]
+    ///
+    /// Returns `None` if out of the supported range.
+    #[inline]
+    #[cfg(feature = "time")]
+    pub fn date_found_offsetdatetime(&self) -> Option<OffsetDateTime> {
+        util::offset_date_time(self.date_found)
+    }
 }
 
 /// Pool stats
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq)]
+#[non_exhaustive]
 pub struct PoolStats {
+    /// Number of active workers in the pool
+    pub pool_active_workers: u64,
     /// Pool hash rate for the last 5 minutes
     pub pool_5m_hash_rate: HashRate,
     /// Pool hash rate for the last 60 minutes
@@ -129,6 +681,13 @@ pub struct PoolStats {
     pub blocks: HashMap<String, Block>,
     /// FPPS rate
     pub fpps_rate: f64,
+    /// Network difficulty
+    ///
+    /// Not part of the documented API response: `None` unless the endpoint reports it.
+    pub network_difficulty: Option<f64>,
+    /// Fields not modeled by this crate (yet), emitted back when serialized
+    #[cfg(feature = "extra-fields")]
+    pub extra: HashMap<String, Value>,
 }
 
 impl<'de> Deserialize<'de> for PoolStats {
@@ -139,384 +698,2763 @@ impl<'de> Deserialize<'de> for PoolStats {
         #[derive(Deserialize)]
         struct Helper {
             hash_rate_unit: HashRateUnit,
+            #[serde(default)]
+            pool_active_workers: u64,
+            #[serde(deserialize_with = "deserialize_number_from_string")]
             pool_5m_hash_rate: f64,
+            #[serde(deserialize_with = "deserialize_number_from_string")]
             pool_60m_hash_rate: f64,
+            #[serde(deserialize_with = "deserialize_number_from_string")]
             pool_24h_hash_rate: f64,
             update_ts: u64,
+            #[serde(default)]
             blocks: HashMap<String, Block>,
+            #[serde(deserialize_with = "deserialize_number_with_unit")]
             fpps_rate: f64,
+            #[serde(default, alias = "difficulty")]
+            network_difficulty: Option<f64>,
+            #[cfg(feature = "extra-fields")]
+            #[serde(flatten)]
+            extra: HashMap<String, Value>,
         }
 
-        let helper: Helper = Helper::deserialize(deserializer)?;
+        let mut helper: Helper = Helper::deserialize(deserializer)?;
+
+        for (height, block) in helper.blocks.iter_mut() {
+            block.height = height.parse().ok();
+            // The block carries no unit: it shares the pool one
+            block.pool_scoring_hash_rate =
+                HashRate::new(helper.hash_rate_unit, block.pool_scoring_hash_rate.value());
+        }
 
         Ok(Self {
+            pool_active_workers: helper.pool_active_workers,
             pool_5m_hash_rate: HashRate::new(helper.hash_rate_unit, helper.pool_5m_hash_rate),
             pool_60m_hash_rate: HashRate::new(helper.hash_rate_unit, helper.pool_60m_hash_rate),
             pool_24h_hash_rate: HashRate::new(helper.hash_rate_unit, helper.pool_24h_hash_rate),
             update_ts: helper.update_ts,
             blocks: helper.blocks,
             fpps_rate: helper.fpps_rate,
+            network_difficulty: helper.network_difficulty,
+            #[cfg(feature = "extra-fields")]
+            extra: helper.extra,
         })
     }
 }
 
-/// User profile
-#[derive(Debug, Clone, PartialEq, PartialOrd)]
-pub struct UserProfile {
-    /// Cumulative all-time reward
-    pub all_time_reward: f64,
-    /// Average hash rate for the last 5 minutes
-    pub hash_rate_5m: HashRate,
-    /// Average hash rate for the last 60 minutes
-    pub hash_rate_60m: HashRate,
-    /// Average hash rate for the last 24 hours
-    pub hash_rate_24h: HashRate,
-    /// Average hash rate for the previous UTC day
-    pub hash_rate_yesterday: HashRate,
-    /// Number of workers with `low` state
-    pub low_workers: u32,
-    /// Number of workers with `off` state
-    pub off_workers: u32,
-    /// Number of workers with `ok` state
-    pub ok_workers: u32,
-    /// Number of workers with disabled monitoring
-    pub dis_workers: u32,
-    /// Current reward balance
-    pub current_balance: f64,
-    /// Confirmed reward for this day
-    pub today_reward: f64,
-    /// Estimated reward for the current block
-    pub estimated_reward: f64,
-    /// Active shares for last 5 minutes
-    pub shares_5m: u32,
-    /// Active shares for last 60 minutes
-    pub shares_60m: u32,
-    /// Active shares for last 24 hours
-    pub shares_24h: u32,
-    /// Active shares for yesterday
-    pub shares_yesterday: u32,
-}
-
-impl<'de> Deserialize<'de> for UserProfile {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+/// Serialized in the API wire format: hash rates are converted to the unit of the 5 minutes one,
+/// emitted once as `hash_rate_unit`.
+///
+/// With the `extra-fields` feature, the fields not modeled by this crate are emitted back too.
+impl Serialize for PoolStats {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
-        D: Deserializer<'de>,
+        S: Serializer,
     {
-        #[derive(Deserialize)]
-        struct Helper {
-            #[serde(deserialize_with = "deserialize_number_from_string")]
-            all_time_reward: f64,
-            hash_rate_unit: HashRateUnit,
-            hash_rate_5m: f64,
-            hash_rate_60m: f64,
-            hash_rate_24h: f64,
-            hash_rate_yesterday: f64,
-            low_workers: u32,
-            off_workers: u32,
-            ok_workers: u32,
-            dis_workers: u32,
-            #[serde(deserialize_with = "deserialize_number_from_string")]
-            current_balance: f64,
-            #[serde(deserialize_with = "deserialize_number_from_string")]
-            today_reward: f64,
-            #[serde(deserialize_with = "deserialize_number_from_string")]
-            estimated_reward: f64,
-            shares_5m: u32,
-            shares_60m: u32,
-            shares_24h: u32,
-            shares_yesterday: u32,
+        #[derive(Serialize)]
+        struct BlockHelper {
+            date_found: u64,
+            mining_duration: u32,
+            total_shares: u64,
+            state: BlockState,
+            confirmations_left: u32,
+            value: f64,
+            user_reward: f64,
+            pool_scoring_hash_rate: f64,
         }
 
-        let helper: Helper = Helper::deserialize(deserializer)?;
-
-        Ok(Self {
-            all_time_reward: helper.all_time_reward,
-            hash_rate_5m: HashRate::new(helper.hash_rate_unit, helper.hash_rate_5m),
-            hash_rate_60m: HashRate::new(helper.hash_rate_unit, helper.hash_rate_60m),
-            hash_rate_24h: HashRate::new(helper.hash_rate_unit, helper.hash_rate_24h),
-            hash_rate_yesterday: HashRate::new(helper.hash_rate_unit, helper.hash_rate_yesterday),
-            low_workers: helper.low_workers,
-            off_workers: helper.off_workers,
-            ok_workers: helper.ok_workers,
-            dis_workers: helper.dis_workers,
-            current_balance: helper.current_balance,
-            today_reward: helper.today_reward,
-            estimated_reward: helper.estimated_reward,
-            shares_5m: helper.shares_5m,
-            shares_60m: helper.shares_60m,
-            shares_24h: helper.shares_24h,
-            shares_yesterday: helper.shares_yesterday,
+        #[derive(Serialize)]
+        struct Helper<'a> {
+            hash_rate_unit: HashRateUnit,
+            pool_active_workers: u64,
+            pool_5m_hash_rate: f64,
+            pool_60m_hash_rate: f64,
+            pool_24h_hash_rate: f64,
+            update_ts: u64,
+            blocks: HashMap<&'a str, BlockHelper>,
+            fpps_rate: f64,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            network_difficulty: Option<f64>,
+            #[cfg(feature = "extra-fields")]
+            #[serde(flatten)]
+            extra: &'a HashMap<String, Value>,
+        }
+
+        let unit: HashRateUnit = self.pool_5m_hash_rate.unit();
+
+        // Avoid the conversion round-trip (and its rounding) when the unit already matches
+        let value = |hash_rate: &HashRate| {
+            if hash_rate.unit() == unit {
+                hash_rate.value()
+            } else {
+                hash_rate.convert(unit).value()
+            }
+        };
+
+        let helper: Helper = Helper {
+            hash_rate_unit: unit,
+            pool_active_workers: self.pool_active_workers,
+            pool_5m_hash_rate: self.pool_5m_hash_rate.value(),
+            pool_60m_hash_rate: value(&self.pool_60m_hash_rate),
+            pool_24h_hash_rate: value(&self.pool_24h_hash_rate),
+            update_ts: self.update_ts,
+            blocks: self
+                .blocks
+                .iter()
+                .map(|(height, block)| {
+                    let block: BlockHelper = BlockHelper {
+                        date_found: block.date_found,
+                        mining_duration: block.mining_duration,
+                        total_shares: block.total_shares,
+                        state: block.state,
+                        confirmations_left: block.confirmations_left,
+                        value: block.value,
+                        user_reward: block.user_reward,
+                        pool_scoring_hash_rate: value(&block.pool_scoring_hash_rate),
+                    };
+                    (height.as_str(), block)
+                })
+                .collect(),
+            fpps_rate: self.fpps_rate,
+            network_difficulty: self.network_difficulty,
+            #[cfg(feature = "extra-fields")]
+            extra: &self.extra,
+        };
+
+        helper.serialize(serializer)
+    }
+}
+
+impl PoolStats {
+    /// Get [`update_ts`](PoolStats::update_ts) as UTC [`OffsetDateTime`]
+    ///
+    /// Returns `None` if out of the supported range.
+    #[inline]
+    #[cfg(feature = "time")]
+    pub fn update_ts_offsetdatetime(&self) -> Option<OffsetDateTime> {
+        util::offset_date_time(self.update_ts)
+    }
+
+    /// Get confirmed blocks
+    #[inline]
+    pub fn confirmed_blocks(&self) -> impl Iterator<Item = (&String, &Block)> {
+        self.blocks_by_state(BlockState::Confirmed)
+    }
+
+    /// Get blocks waiting for confirmations
+    #[inline]
+    pub fn pending_blocks(&self) -> impl Iterator<Item = (&String, &Block)> {
+        self.blocks_by_state(BlockState::Pending)
+    }
+
+    /// Get orphaned blocks
+    #[inline]
+    pub fn orphaned_blocks(&self) -> impl Iterator<Item = (&String, &Block)> {
+        self.blocks_by_state(BlockState::Orphaned)
+    }
+
+    fn blocks_by_state(&self, state: BlockState) -> impl Iterator<Item = (&String, &Block)> {
+        self.blocks
+            .iter()
+            .filter(move |(_, block)| block.state == state)
+    }
+
+    /// Check if the 5 minutes pool hash rate is more than `threshold_pct` percent below the 60 minutes one
+    ///
+    /// Returns `false` if the 60 minutes hash rate is zero.
+    pub fn is_hashrate_dropping(&self, threshold_pct: f64) -> bool {
+        let hash_rate_5m: f64 = self.pool_5m_hash_rate.to_hashes();
+        let hash_rate_60m: f64 = self.pool_60m_hash_rate.to_hashes();
+
+        if hash_rate_60m == 0.0 {
+            return false;
+        }
+
+        let drop_pct: f64 = (hash_rate_60m - hash_rate_5m) / hash_rate_60m * 100.0;
+        drop_pct > threshold_pct
+    }
+
+    /// Get blocks found after `ts` (unix time), sorted by [`date_found`](Block::date_found)
+    pub fn blocks_since(&self, ts: u64) -> Vec<&Block> {
+        let mut blocks: Vec<&Block> = self
+            .blocks
+            .values()
+            .filter(|block| block.date_found > ts)
+            .collect();
+        blocks.sort_by_key(|block| block.date_found);
+        blocks
+    }
+
+    /// Get blocks found within the last `within`, with their height, most recent first
+    ///
+    /// Blocks dated in the future (clock skew) are included.
+    /// Blocks with a non-numeric height key are skipped.
+    pub fn recent_blocks(&self, within: Duration) -> Vec<(u64, &Block)> {
+        let now: u64 = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let cutoff: u64 = now.saturating_sub(within.as_secs());
+
+        let mut blocks: Vec<(u64, &Block)> = self
+            .blocks
+            .iter()
+            .filter(|(_, block)| block.date_found >= cutoff)
+            .filter_map(|(height, block)| Some((height.parse::<u64>().ok()?, block)))
+            .collect();
+        blocks.sort_by(|(_, a), (_, b)| b.date_found.cmp(&a.date_found));
+        blocks
+    }
+
+    /// Average interval between the blocks found by the pool
+    ///
+    /// Computed from the [`date_found`](Block::date_found) of the first and last blocks.
+    /// Returns `None` if there are less than 2 blocks.
+    pub fn average_block_interval(&self) -> Option<Duration> {
+        if self.blocks.len() < 2 {
+            return None;
+        }
+
+        let (first, last) = self.block_window()?;
+        let intervals: u64 = self.blocks.len() as u64 - 1;
+
+        Some(Duration::from_secs((last - first) / intervals))
+    }
+
+    /// Expected time for the pool to find a block, at the 24 hours pool hash rate
+    ///
+    /// Computed as `difficulty * 2^32 / hash rate`.
+    /// Returns `None` if the [`network_difficulty`](PoolStats::network_difficulty) isn't reported or the hash rate is zero.
+    pub fn expected_block_interval(&self) -> Option<Duration> {
+        let difficulty: f64 = self.network_difficulty?;
+        let hashes: f64 = self.pool_24h_hash_rate.to_hashes();
+
+        if hashes <= 0.0 {
+            return None;
+        }
+
+        Duration::try_from_secs_f64(difficulty * 2f64.powi(32) / hashes).ok()
+    }
+
+    /// Get round duration stats, from the [`mining_duration`](Block::mining_duration) of the blocks
+    ///
+    /// Returns `None` if there are no blocks.
+    pub fn round_stats(&self) -> Option<RoundStats> {
+        let durations = self.blocks.values().map(|b| u64::from(b.mining_duration));
+
+        let count: usize = self.blocks.len();
+        let min: u64 = durations.clone().min()?;
+        let max: u64 = durations.clone().max()?;
+        let total: u64 = durations.sum();
+
+        Some(RoundStats {
+            count,
+            avg_duration: Duration::from_secs(total / count as u64),
+            min_duration: Duration::from_secs(min),
+            max_duration: Duration::from_secs(max),
         })
     }
+
+    /// Get the earliest and latest `date_found` among the blocks
+    ///
+    /// Returns `None` if there are no blocks.
+    pub fn block_window(&self) -> Option<(u64, u64)> {
+        let first: u64 = self.blocks.values().map(|b| b.date_found).min()?;
+        let last: u64 = self.blocks.values().map(|b| b.date_found).max()?;
+        Some((first, last))
+    }
+
+    /// Get blocks sorted by height (ascending)
+    ///
+    /// Blocks with a non-numeric height key are skipped.
+    pub fn blocks_sorted_by_height(&self) -> Vec<(u64, &Block)> {
+        let mut blocks: Vec<(u64, &Block)> = self
+            .blocks
+            .iter()
+            .filter_map(|(height, block)| Some((height.parse::<u64>().ok()?, block)))
+            .collect();
+        blocks.sort_by_key(|(height, _)| *height);
+        blocks
+    }
 }
 
-/// Daily reward
-#[derive(Debug, Clone, PartialEq, PartialOrd, Deserialize)]
-pub struct DailyReward {
-    /// Unix time (the first second of the date)
-    pub date: u64,
-    /// The sum of all reward types for the day
-    #[serde(deserialize_with = "deserialize_number_from_string")]
-    pub total_reward: f64,
-    /// The standard mining reward
-    #[serde(deserialize_with = "deserialize_number_from_string")]
-    pub mining_reward: f64,
-    /// The amount refunded (pool fee refund) for mining with Braiins OS
-    #[serde(deserialize_with = "deserialize_number_from_string")]
-    pub bos_plus_reward: f64,
-    /// Bonus received by being referred to Braiins OS
-    #[serde(deserialize_with = "deserialize_number_from_string")]
-    pub referral_bonus: f64,
-    /// Reward earned for HR referred to Braiins OS
-    #[serde(deserialize_with = "deserialize_number_from_string")]
-    pub referral_reward: f64,
-    /// Calculation date timestamp
-    pub calculation_date: u64,
+/// Round duration stats
+///
+/// See [`PoolStats::round_stats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoundStats {
+    /// Number of rounds (blocks)
+    pub count: usize,
+    /// Average round duration
+    pub avg_duration: Duration,
+    /// Shortest round duration
+    pub min_duration: Duration,
+    /// Longest round duration
+    pub max_duration: Duration,
 }
 
-/// Daily rewards
-#[derive(Debug, Clone, PartialEq, PartialOrd, Deserialize)]
-pub struct DailyRewards {
-    /// Daily rewards
-    pub daily_rewards: Vec<DailyReward>,
+/// Hashrate point
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct HashratePoint {
+    /// Unix time
+    pub timestamp: u64,
+    /// Hash rate
+    pub hash_rate: HashRate,
 }
 
-/// Worker
-#[derive(Debug, Clone, PartialEq, PartialOrd)]
-pub struct Worker {
-    /// State of the worker (`ok`/`low`/`off`/`dis`)
-    pub state: String,
-    /// Unix time of the last accepted share
-    pub last_share: u64,
-    /// Current scoring hash rate
-    pub hash_rate_scoring: HashRate,
+impl<'de> Deserialize<'de> for HashratePoint {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct Helper {
+            timestamp: u64,
+            hash_rate_unit: HashRateUnit,
+            #[serde(deserialize_with = "deserialize_number_from_string")]
+            hash_rate: f64,
+        }
+
+        let helper: Helper = Helper::deserialize(deserializer)?;
+
+        Ok(Self {
+            timestamp: helper.timestamp,
+            hash_rate: HashRate::new(helper.hash_rate_unit, helper.hash_rate),
+        })
+    }
+}
+
+impl HashratePoint {
+    /// Get [`timestamp`](HashratePoint::timestamp) as UTC [`OffsetDateTime`]
+    ///
+    /// Returns `None` if out of the supported range.
+    #[inline]
+    #[cfg(feature = "time")]
+    pub fn timestamp_offsetdatetime(&self) -> Option<OffsetDateTime> {
+        util::offset_date_time(self.timestamp)
+    }
+}
+
+/// User profile
+#[derive(Debug, Clone, Default, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub struct UserProfile {
+    /// Cumulative all-time reward
+    pub all_time_reward: f64,
     /// Average hash rate for the last 5 minutes
     pub hash_rate_5m: HashRate,
     /// Average hash rate for the last 60 minutes
     pub hash_rate_60m: HashRate,
     /// Average hash rate for the last 24 hours
     pub hash_rate_24h: HashRate,
+    /// Average hash rate for the previous UTC day
+    pub hash_rate_yesterday: HashRate,
+    /// Number of workers with `low` state
+    pub low_workers: u32,
+    /// Number of workers with `off` state
+    pub off_workers: u32,
+    /// Number of workers with `ok` state
+    pub ok_workers: u32,
+    /// Number of workers with disabled monitoring
+    pub dis_workers: u32,
+    /// Current reward balance
+    pub current_balance: f64,
+    /// Confirmed reward for this day
+    pub today_reward: f64,
+    /// Estimated reward for the current block
+    pub estimated_reward: f64,
     /// Active shares for last 5 minutes
-    pub shares_5m: u64,
+    pub shares_5m: u32,
     /// Active shares for last 60 minutes
-    pub shares_60m: u64,
+    pub shares_60m: u32,
     /// Active shares for last 24 hours
-    pub shares_24h: u64,
+    pub shares_24h: u32,
+    /// Active shares for yesterday
+    pub shares_yesterday: u32,
 }
 
-impl<'de> Deserialize<'de> for Worker {
+impl<'de> Deserialize<'de> for UserProfile {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
         #[derive(Deserialize)]
         struct Helper {
-            state: String,
-            last_share: u64,
+            #[serde(default, deserialize_with = "deserialize_number_from_string")]
+            all_time_reward: f64,
             hash_rate_unit: HashRateUnit,
-            hash_rate_scoring: f64,
+            #[serde(deserialize_with = "deserialize_number_from_string")]
             hash_rate_5m: f64,
+            #[serde(deserialize_with = "deserialize_number_from_string")]
             hash_rate_60m: f64,
+            #[serde(deserialize_with = "deserialize_number_from_string")]
             hash_rate_24h: f64,
-            shares_5m: u64,
-            shares_60m: u64,
-            shares_24h: u64,
+            #[serde(default, deserialize_with = "deserialize_number_from_string")]
+            hash_rate_yesterday: f64,
+            #[serde(default)]
+            low_workers: u32,
+            #[serde(default)]
+            off_workers: u32,
+            #[serde(default)]
+            ok_workers: u32,
+            #[serde(default)]
+            dis_workers: u32,
+            #[serde(default, deserialize_with = "deserialize_number_from_string")]
+            current_balance: f64,
+            #[serde(default, deserialize_with = "deserialize_number_from_string")]
+            today_reward: f64,
+            #[serde(default, deserialize_with = "deserialize_number_from_string")]
+            estimated_reward: f64,
+            #[serde(default)]
+            shares_5m: u32,
+            #[serde(default)]
+            shares_60m: u32,
+            #[serde(default)]
+            shares_24h: u32,
+            #[serde(default)]
+            shares_yesterday: u32,
         }
 
         let helper: Helper = Helper::deserialize(deserializer)?;
 
         Ok(Self {
-            state: helper.state,
-            last_share: helper.last_share,
-            hash_rate_scoring: HashRate::new(helper.hash_rate_unit, helper.hash_rate_scoring),
+            all_time_reward: helper.all_time_reward,
             hash_rate_5m: HashRate::new(helper.hash_rate_unit, helper.hash_rate_5m),
             hash_rate_60m: HashRate::new(helper.hash_rate_unit, helper.hash_rate_60m),
             hash_rate_24h: HashRate::new(helper.hash_rate_unit, helper.hash_rate_24h),
+            hash_rate_yesterday: HashRate::new(helper.hash_rate_unit, helper.hash_rate_yesterday),
+            low_workers: helper.low_workers,
+            off_workers: helper.off_workers,
+            ok_workers: helper.ok_workers,
+            dis_workers: helper.dis_workers,
+            current_balance: helper.current_balance,
+            today_reward: helper.today_reward,
+            estimated_reward: helper.estimated_reward,
             shares_5m: helper.shares_5m,
             shares_60m: helper.shares_60m,
             shares_24h: helper.shares_24h,
+            shares_yesterday: helper.shares_yesterday,
         })
     }
 }
 
-/// Workers
-#[derive(Debug, Clone, PartialEq, Deserialize)]
-pub struct Workers {
-    /// Workers
-    pub workers: HashMap<String, Worker>,
+/// Slow-changing subset of [`UserProfile`]
+///
+/// Can be cached longer than the volatile data (hash rates, shares and current rewards).
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct UserProfileSummary {
+    /// Cumulative all-time reward
+    pub all_time_reward: f64,
+    /// Current reward balance
+    pub current_balance: f64,
+    /// Number of workers with `low` state
+    pub low_workers: u32,
+    /// Number of workers with `off` state
+    pub off_workers: u32,
+    /// Number of workers with `ok` state
+    pub ok_workers: u32,
+    /// Number of workers with disabled monitoring
+    pub dis_workers: u32,
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_pool_stats_deserialization() {
-        let json = r#"{
-    "btc": {
-        "hash_rate_unit": "Gh/s",
-        "pool_active_workers": 1,
-        "pool_5m_hash_rate": 5727000000.746604,
-        "pool_60m_hash_rate": 5617000000.99422,
-        "pool_24h_hash_rate": 5517000000.88519,
-        "update_ts": 1699938300,
-        "blocks": {
-            "549753": {
-                "date_found": 1542002919,
-                "mining_duration": 3423,
-                "total_shares": 4640771710739,
-                "state": "confirmed",
-                "confirmations_left": 0,
-                "value": "12.92594863",
-                "user_reward": "0.00006194",
-                "pool_scoring_hash_rate": 5878745444.967269
-            }
-        },
-        "fpps_rate": 0.00000241
+impl UserProfile {
+    /// Check if a [`UserProfile`] field is volatile (changes between every pool update)
+    ///
+    /// Hash rates, shares, `today_reward` and `estimated_reward` are volatile.
+    /// The other fields are part of the [`UserProfileSummary`] and change slowly.
+    /// Returns `false` for unknown fields.
+    pub fn is_volatile_field(field: &str) -> bool {
+        matches!(
+            field,
+            "hash_rate_5m"
+                | "hash_rate_60m"
+                | "hash_rate_24h"
+                | "hash_rate_yesterday"
+                | "today_reward"
+                | "estimated_reward"
+                | "shares_5m"
+                | "shares_60m"
+                | "shares_24h"
+                | "shares_yesterday"
+        )
     }
-}"#;
-        let user_profile: BtcResponse<PoolStats> = serde_json::from_str(json).unwrap();
-        assert_eq!(
-            user_profile.btc,
-            PoolStats {
-                pool_5m_hash_rate: HashRate::new(HashRateUnit::GH, 5727000000.746604),
-                pool_60m_hash_rate: HashRate::new(HashRateUnit::GH, 5617000000.99422),
-                pool_24h_hash_rate: HashRate::new(HashRateUnit::GH, 5517000000.88519),
-                update_ts: 1699938300,
-                blocks: HashMap::from([(
-                    String::from("549753"),
-                    Block {
-                        date_found: 1542002919,
-                        mining_duration: 3423,
-                        total_shares: 4640771710739,
-                        state: String::from("confirmed"),
-                        confirmations_left: 0,
-                        value: 12.92594863,
-                        user_reward: 0.00006194,
-                        pool_scoring_hash_rate: 5878745444.967269
-                    }
-                )]),
-                fpps_rate: 0.00000241
-            }
-        );
+
+    /// Get the slow-changing subset of the profile
+    pub fn static_summary(&self) -> UserProfileSummary {
+        UserProfileSummary {
+            all_time_reward: self.all_time_reward,
+            current_balance: self.current_balance,
+            low_workers: self.low_workers,
+            off_workers: self.off_workers,
+            ok_workers: self.ok_workers,
+            dis_workers: self.dis_workers,
+        }
+    }
+
+    /// Today's reward per TH/s of the 24h average hash rate
+    ///
+    /// Computed as `today_reward / hash_rate_24h` (in TH/s).
+    /// Returns `0.0` if the 24h hash rate is zero.
+    ///
+    /// Note that the two windows don't match: `today_reward` covers the current UTC day so far,
+    /// while `hash_rate_24h` is a rolling average over the last 24 hours.
+    /// The result is therefore only comparable with the pool FPPS rate at the end of the day.
+    pub fn reward_per_th_24h(&self) -> f64 {
+        let th: f64 = self.hash_rate_24h.convert(HashRateUnit::TH).value();
+
+        if th == 0.0 {
+            return 0.0;
+        }
+
+        self.today_reward / th
+    }
+
+    /// Realized FPPS rate of the account today, in **BTC per TH/s per day**
+    ///
+    /// Compare with [`PoolStats::fpps_rate`] to check if the account gets the advertised rate:
+    ///
+    /// `realized_fpps = today_reward / (hash_rate_24h (TH/s) * seconds_elapsed_today / 86400)`
+    ///
+    /// `hash_rate_24h` approximates the average hash rate since midnight (UTC).
+    /// Returns `0.0` if the hash rate or the elapsed time is zero.
+    pub fn realized_fpps(&self, seconds_elapsed_today: u64) -> f64 {
+        let th: f64 = self.hash_rate_24h.convert(HashRateUnit::TH).value();
+        let days: f64 = seconds_elapsed_today as f64 / 86400.0;
+        let th_days: f64 = th * days;
+
+        if th_days == 0.0 {
+            return 0.0;
+        }
+
+        self.today_reward / th_days
+    }
+
+    /// Progress of `today_reward` toward `estimated_reward`, as a ratio (multiply by 100 for a percentage)
+    ///
+    /// Not clamped: exceeds `1.0` when the confirmed reward is above the estimate.
+    /// Returns `0.0` if the estimated reward is zero.
+    pub fn estimated_reward_progress(&self) -> f64 {
+        if self.estimated_reward == 0.0 {
+            return 0.0;
+        }
+
+        self.today_reward / self.estimated_reward
+    }
+
+    /// Reward still expected to reach `estimated_reward` (`estimated_reward - today_reward`, clamped at zero)
+    #[inline]
+    pub fn remaining_estimated(&self) -> f64 {
+        (self.estimated_reward - self.today_reward).max(0.0)
+    }
+
+    /// Forecast of the reward at the end of the current UTC day
+    ///
+    /// Linearly projects `today_reward` to a full day: `today_reward * 86400 / seconds_elapsed_today`.
+    ///
+    /// `seconds_elapsed_today` is clamped to `1..=86400`:
+    /// a value of `0` or past the end of the day returns `today_reward` as is.
+    pub fn forecast_end_of_day_reward(&self, seconds_elapsed_today: u64) -> f64 {
+        if seconds_elapsed_today == 0 || seconds_elapsed_today >= 86400 {
+            return self.today_reward;
+        }
+
+        self.today_reward * 86400.0 / seconds_elapsed_today as f64
+    }
+}
+
+/// Daily reward
+#[derive(Debug, Clone, Default, PartialEq, PartialOrd, Deserialize)]
+#[non_exhaustive]
+pub struct DailyReward {
+    /// Unix time (the first second of the date)
+    pub date: u64,
+    /// The sum of all reward types for the day
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub total_reward: f64,
+    /// The standard mining reward
+    #[serde(default, deserialize_with = "deserialize_number_from_string")]
+    pub mining_reward: f64,
+    /// The amount refunded (pool fee refund) for mining with Braiins OS
+    #[serde(default, deserialize_with = "deserialize_number_from_string")]
+    pub bos_plus_reward: f64,
+    /// Bonus received by being referred to Braiins OS
+    #[serde(default, deserialize_with = "deserialize_number_from_string")]
+    pub referral_bonus: f64,
+    /// Reward earned for HR referred to Braiins OS
+    #[serde(default, deserialize_with = "deserialize_number_from_string")]
+    pub referral_reward: f64,
+    /// Calculation date timestamp
+    #[serde(default)]
+    pub calculation_date: u64,
+}
+
+impl DailyReward {
+    /// Get [`date`](DailyReward::date) as UTC [`OffsetDateTime`]
+    ///
+    /// Returns `None` if out of the supported range.
+    #[inline]
+    #[cfg(feature = "time")]
+    pub fn date_offsetdatetime(&self) -> Option<OffsetDateTime> {
+        util::offset_date_time(self.date)
+    }
+
+    /// Get [`calculation_date`](DailyReward::calculation_date) as UTC [`OffsetDateTime`]
+    ///
+    /// Returns `None` if out of the supported range.
+    #[inline]
+    #[cfg(feature = "time")]
+    pub fn calculation_date_offsetdatetime(&self) -> Option<OffsetDateTime> {
+        util::offset_date_time(self.calculation_date)
+    }
+
+    /// Fraction of the total reward that comes from the Braiins OS pool fee refund
+    ///
+    /// Returns `0.0` if the total reward is zero.
+    pub fn bos_plus_fraction(&self) -> f64 {
+        if self.total_reward == 0.0 {
+            return 0.0;
+        }
+
+        self.bos_plus_reward / self.total_reward
+    }
+}
+
+/// Daily rewards
+#[derive(Debug, Clone, Default, PartialEq, PartialOrd, Deserialize)]
+#[non_exhaustive]
+pub struct DailyRewards {
+    /// Daily rewards
+    pub daily_rewards: Vec<DailyReward>,
+}
+
+impl DailyRewards {
+    /// Fraction of the total reward, over all days, that comes from the Braiins OS pool fee refund
+    ///
+    /// Returns `0.0` if the total reward is zero.
+    pub fn bos_plus_fraction(&self) -> f64 {
+        let (bos_plus, total) =
+            self.daily_rewards
+                .iter()
+                .fold((0.0, 0.0), |(bos_plus, total), reward| {
+                    (
+                        bos_plus + reward.bos_plus_reward,
+                        total + reward.total_reward,
+                    )
+                });
+
+        if total == 0.0 {
+            return 0.0;
+        }
+
+        bos_plus / total
+    }
+
+    /// Get a page of `limit` rewards, starting at `offset`
+    ///
+    /// Pages follow the order of the rewards, as returned by the API.
+    pub fn page(&self, offset: u32, limit: u32) -> DailyRewardsPage {
+        let len: usize = self.daily_rewards.len();
+        let start: usize = (offset as usize).min(len);
+        let end: usize = start.saturating_add(limit as usize).min(len);
+
+        DailyRewardsPage {
+            daily_rewards: self.daily_rewards[start..end].to_vec(),
+            offset,
+            has_more: end < len,
+        }
+    }
+
+    /// Iterate over pages of `limit` rewards
+    ///
+    /// A `limit` of zero yields no pages.
+    pub fn pages(&self, limit: u32) -> impl Iterator<Item = DailyRewardsPage> + '_ {
+        let mut offset: Option<u32> = (limit > 0).then_some(0);
+        std::iter::from_fn(move || {
+            let page: DailyRewardsPage = self.page(offset?, limit);
+            offset = page.has_more.then_some(page.offset.saturating_add(limit));
+            Some(page)
+        })
+    }
+
+    /// Merge with other daily rewards
+    ///
+    /// The result is sorted by date (oldest first) and contains one reward per date:
+    /// on duplicates, the one with the most recent `calculation_date` is kept (`other` on ties).
+    pub fn merge(self, other: DailyRewards) -> DailyRewards {
+        let mut by_date: BTreeMap<u64, DailyReward> = BTreeMap::new();
+
+        for reward in self.daily_rewards.into_iter().chain(other.daily_rewards) {
+            match by_date.get(&reward.date) {
+                Some(existing) if existing.calculation_date > reward.calculation_date => {}
+                _ => {
+                    by_date.insert(reward.date, reward);
+                }
+            }
+        }
+
+        DailyRewards {
+            daily_rewards: by_date.into_values().collect(),
+        }
+    }
+}
+
+/// Page of daily rewards
+///
+/// The API returns the whole history at once: pages are sliced client-side (see [`DailyRewards::page`]).
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct DailyRewardsPage {
+    /// Daily rewards
+    pub daily_rewards: Vec<DailyReward>,
+    /// Offset of the first reward of the page
+    pub offset: u32,
+    /// Whether there are more rewards after this page
+    pub has_more: bool,
+}
+
+/// Worker state
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum WorkerState {
+    /// Worker is hashing normally
+    Ok,
+    /// Worker hash rate is low
+    Low,
+    /// Worker is offline
+    Off,
+    /// Worker monitoring is disabled
+    Disabled,
+    /// Unknown state
+    #[default]
+    Unknown,
+}
+
+impl WorkerState {
+    /// Get state as string (`ok`/`low`/`off`/`dis`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Ok => "ok",
+            Self::Low => "low",
+            Self::Off => "off",
+            Self::Disabled => "dis",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+impl From<&str> for WorkerState {
+    fn from(state: &str) -> Self {
+        match state {
+            "ok" => Self::Ok,
+            "low" => Self::Low,
+            "off" => Self::Off,
+            "dis" => Self::Disabled,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+impl Serialize for WorkerState {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for WorkerState {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let state: String = String::deserialize(deserializer)?;
+        Ok(Self::from(state.as_str()))
+    }
+}
+
+/// Worker
+///
+/// The API only reports **accepted** shares: rejected and stale share counts aren't exposed,
+/// so reject rates can't be computed from it (check the miner's own stats instead).
+#[derive(Debug, Clone, Default, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub struct Worker {
+    /// State of the worker
+    pub state: WorkerState,
+    /// Unix time of the last accepted share
+    pub last_share: u64,
+    /// Current scoring hash rate
+    pub hash_rate_scoring: HashRate,
+    /// Average hash rate for the last 5 minutes
+    pub hash_rate_5m: HashRate,
+    /// Average hash rate for the last 60 minutes
+    pub hash_rate_60m: HashRate,
+    /// Average hash rate for the last 24 hours
+    pub hash_rate_24h: HashRate,
+    /// Active shares for last 5 minutes
+    pub shares_5m: u64,
+    /// Active shares for last 60 minutes
+    pub shares_60m: u64,
+    /// Active shares for last 24 hours
+    pub shares_24h: u64,
+}
+
+impl<'de> Deserialize<'de> for Worker {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct Helper {
+            state: WorkerState,
+            last_share: u64,
+            hash_rate_unit: HashRateUnit,
+            #[serde(deserialize_with = "deserialize_number_from_string")]
+            hash_rate_scoring: f64,
+            #[serde(deserialize_with = "deserialize_number_from_string")]
+            hash_rate_5m: f64,
+            #[serde(deserialize_with = "deserialize_number_from_string")]
+            hash_rate_60m: f64,
+            #[serde(deserialize_with = "deserialize_number_from_string")]
+            hash_rate_24h: f64,
+            #[serde(default)]
+            shares_5m: u64,
+            #[serde(default)]
+            shares_60m: u64,
+            #[serde(default)]
+            shares_24h: u64,
+        }
+
+        let helper: Helper = Helper::deserialize(deserializer)?;
+
+        Ok(Self {
+            state: helper.state,
+            last_share: helper.last_share,
+            hash_rate_scoring: HashRate::new(helper.hash_rate_unit, helper.hash_rate_scoring),
+            hash_rate_5m: HashRate::new(helper.hash_rate_unit, helper.hash_rate_5m),
+            hash_rate_60m: HashRate::new(helper.hash_rate_unit, helper.hash_rate_60m),
+            hash_rate_24h: HashRate::new(helper.hash_rate_unit, helper.hash_rate_24h),
+            shares_5m: helper.shares_5m,
+            shares_60m: helper.shares_60m,
+            shares_24h: helper.shares_24h,
+        })
+    }
+}
+
+/// Serialized in the API wire format: hash rates are converted to the unit of the scoring one,
+/// emitted once as `hash_rate_unit`.
+impl Serialize for Worker {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[derive(Serialize)]
+        struct Helper {
+            state: WorkerState,
+            last_share: u64,
+            hash_rate_unit: HashRateUnit,
+            hash_rate_scoring: f64,
+            hash_rate_5m: f64,
+            hash_rate_60m: f64,
+            hash_rate_24h: f64,
+            shares_5m: u64,
+            shares_60m: u64,
+            shares_24h: u64,
+        }
+
+        let unit: HashRateUnit = self.hash_rate_scoring.unit();
+
+        // Avoid the conversion round-trip (and its rounding) when the unit already matches
+        let value = |hash_rate: &HashRate| {
+            if hash_rate.unit() == unit {
+                hash_rate.value()
+            } else {
+                hash_rate.convert(unit).value()
+            }
+        };
+
+        let helper: Helper = Helper {
+            state: self.state,
+            last_share: self.last_share,
+            hash_rate_unit: unit,
+            hash_rate_scoring: self.hash_rate_scoring.value(),
+            hash_rate_5m: value(&self.hash_rate_5m),
+            hash_rate_60m: value(&self.hash_rate_60m),
+            hash_rate_24h: value(&self.hash_rate_24h),
+            shares_5m: self.shares_5m,
+            shares_60m: self.shares_60m,
+            shares_24h: self.shares_24h,
+        };
+
+        helper.serialize(serializer)
+    }
+}
+
+impl Worker {
+    /// Get [`last_share`](Worker::last_share) as UTC [`OffsetDateTime`]
+    ///
+    /// Returns `None` if out of the supported range.
+    #[inline]
+    #[cfg(feature = "time")]
+    pub fn last_share_offsetdatetime(&self) -> Option<OffsetDateTime> {
+        util::offset_date_time(self.last_share)
+    }
+
+    /// Time elapsed since the last accepted share
+    ///
+    /// Saturates to zero if `last_share` is in the future relative to the system clock.
+    pub fn last_share_age(&self) -> Duration {
+        match UNIX_EPOCH.checked_add(Duration::from_secs(self.last_share)) {
+            Some(last_share) => SystemTime::now()
+                .duration_since(last_share)
+                .unwrap_or_default(),
+            None => Duration::ZERO,
+        }
+    }
+
+    /// Check if the worker hasn't submitted a share for more than `max_age`
+    ///
+    /// This is independent of the worker [`state`](Worker::state).
+    #[inline]
+    pub fn is_stale(&self, max_age: Duration) -> bool {
+        self.last_share_age() > max_age
+    }
+}
+
+/// Worker borrowing its strings from the input
+///
+/// Useful for read-only processing of large responses without allocating a [`String`] per worker.
+/// Convert to an owned [`Worker`] with [`From`].
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct WorkerRef<'a> {
+    /// State of the worker (`ok`/`low`/`off`/`dis`)
+    pub state: Cow<'a, str>,
+    /// Unix time of the last accepted share
+    pub last_share: u64,
+    /// Current scoring hash rate
+    pub hash_rate_scoring: HashRate,
+    /// Average hash rate for the last 5 minutes
+    pub hash_rate_5m: HashRate,
+    /// Average hash rate for the last 60 minutes
+    pub hash_rate_60m: HashRate,
+    /// Average hash rate for the last 24 hours
+    pub hash_rate_24h: HashRate,
+    /// Active shares for last 5 minutes
+    pub shares_5m: u64,
+    /// Active shares for last 60 minutes
+    pub shares_60m: u64,
+    /// Active shares for last 24 hours
+    pub shares_24h: u64,
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for WorkerRef<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct Helper<'a> {
+            #[serde(borrow)]
+            state: Cow<'a, str>,
+            last_share: u64,
+            hash_rate_unit: HashRateUnit,
+            #[serde(deserialize_with = "deserialize_number_from_string")]
+            hash_rate_scoring: f64,
+            #[serde(deserialize_with = "deserialize_number_from_string")]
+            hash_rate_5m: f64,
+            #[serde(deserialize_with = "deserialize_number_from_string")]
+            hash_rate_60m: f64,
+            #[serde(deserialize_with = "deserialize_number_from_string")]
+            hash_rate_24h: f64,
+            #[serde(default)]
+            shares_5m: u64,
+            #[serde(default)]
+            shares_60m: u64,
+            #[serde(default)]
+            shares_24h: u64,
+        }
+
+        let helper: Helper<'a> = Helper::deserialize(deserializer)?;
+
+        Ok(Self {
+            state: helper.state,
+            last_share: helper.last_share,
+            hash_rate_scoring: HashRate::new(helper.hash_rate_unit, helper.hash_rate_scoring),
+            hash_rate_5m: HashRate::new(helper.hash_rate_unit, helper.hash_rate_5m),
+            hash_rate_60m: HashRate::new(helper.hash_rate_unit, helper.hash_rate_60m),
+            hash_rate_24h: HashRate::new(helper.hash_rate_unit, helper.hash_rate_24h),
+            shares_5m: helper.shares_5m,
+            shares_60m: helper.shares_60m,
+            shares_24h: helper.shares_24h,
+        })
+    }
+}
+
+impl From<WorkerRef<'_>> for Worker {
+    fn from(worker: WorkerRef<'_>) -> Self {
+        Self {
+            state: WorkerState::from(worker.state.as_ref()),
+            last_share: worker.last_share,
+            hash_rate_scoring: worker.hash_rate_scoring,
+            hash_rate_5m: worker.hash_rate_5m,
+            hash_rate_60m: worker.hash_rate_60m,
+            hash_rate_24h: worker.hash_rate_24h,
+            shares_5m: worker.shares_5m,
+            shares_60m: worker.shares_60m,
+            shares_24h: worker.shares_24h,
+        }
+    }
+}
+
+/// Workers
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[non_exhaustive]
+pub struct Workers {
+    /// Workers
+    pub workers: HashMap<String, Worker>,
+}
+
+impl Workers {
+    /// Get a worker by name
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<&Worker> {
+        self.workers.get(name)
+    }
+
+    /// Number of workers
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// Check if there are no workers
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.workers.is_empty()
+    }
+
+    /// Average hash rate per worker for the last 5 minutes (zero if there are no workers)
+    #[inline]
+    pub fn average_hash_rate_5m(&self) -> HashRate {
+        self.average_hash_rate(|w| w.hash_rate_5m)
+    }
+
+    /// Average hash rate per worker for the last 60 minutes (zero if there are no workers)
+    #[inline]
+    pub fn average_hash_rate_60m(&self) -> HashRate {
+        self.average_hash_rate(|w| w.hash_rate_60m)
+    }
+
+    /// Average hash rate per worker for the last 24 hours (zero if there are no workers)
+    #[inline]
+    pub fn average_hash_rate_24h(&self) -> HashRate {
+        self.average_hash_rate(|w| w.hash_rate_24h)
+    }
+
+    fn average_hash_rate<F>(&self, f: F) -> HashRate
+    where
+        F: Fn(&Worker) -> HashRate,
+    {
+        let total: HashRate = self.workers.values().map(f).sum();
+
+        if self.workers.is_empty() {
+            return total;
+        }
+
+        HashRate::new(total.unit, total.value / self.workers.len() as f64)
+    }
+
+    /// Total hash rate for the last 5 minutes
+    #[inline]
+    pub fn total_hash_rate_5m(&self) -> HashRate {
+        self.workers.values().map(|w| w.hash_rate_5m).sum()
+    }
+
+    /// Total hash rate for the last 60 minutes
+    #[inline]
+    pub fn total_hash_rate_60m(&self) -> HashRate {
+        self.workers.values().map(|w| w.hash_rate_60m).sum()
+    }
+
+    /// Total hash rate for the last 24 hours
+    #[inline]
+    pub fn total_hash_rate_24h(&self) -> HashRate {
+        self.workers.values().map(|w| w.hash_rate_24h).sum()
+    }
+
+    /// Total active shares for the last 5 minutes
+    #[inline]
+    pub fn total_shares_5m(&self) -> u64 {
+        self.total_shares(|w| w.shares_5m)
+    }
+
+    /// Total active shares for the last 60 minutes
+    #[inline]
+    pub fn total_shares_60m(&self) -> u64 {
+        self.total_shares(|w| w.shares_60m)
+    }
+
+    /// Total active shares for the last 24 hours
+    #[inline]
+    pub fn total_shares_24h(&self) -> u64 {
+        self.total_shares(|w| w.shares_24h)
+    }
+
+    /// Sum shares, saturating at [`u64::MAX`]
+    fn total_shares<F>(&self, shares: F) -> u64
+    where
+        F: Fn(&Worker) -> u64,
+    {
+        self.workers
+            .values()
+            .fold(0, |total, worker| total.saturating_add(shares(worker)))
+    }
+
+    /// Count workers by state
+    pub fn count_by_state(&self) -> HashMap<WorkerState, usize> {
+        let mut counts: HashMap<WorkerState, usize> = HashMap::new();
+        for worker in self.workers.values() {
+            *counts.entry(worker.state).or_default() += 1;
+        }
+        counts
+    }
+
+    /// Get offline workers
+    pub fn offline(&self) -> Vec<(&String, &Worker)> {
+        self.workers
+            .iter()
+            .filter(|(_, worker)| worker.state == WorkerState::Off)
+            .collect()
+    }
+
+    /// Diff against a `previous` snapshot
+    pub fn diff(&self, previous: &Workers) -> WorkersDiff {
+        let mut diff: WorkersDiff = WorkersDiff::default();
+
+        for (name, worker) in self.workers.iter() {
+            match previous.workers.get(name) {
+                Some(old) if old.state != worker.state => {
+                    diff.changed.push((name.clone(), old.state, worker.state));
+                }
+                Some(..) => {}
+                None => diff.added.push(name.clone()),
+            }
+        }
+
+        for name in previous.workers.keys() {
+            if !self.workers.contains_key(name) {
+                diff.removed.push(name.clone());
+            }
+        }
+
+        diff.added.sort();
+        diff.removed.sort();
+        diff.changed.sort_by(|(a, ..), (b, ..)| a.cmp(b));
+
+        diff
+    }
+}
+
+/// Changes between two [`Workers`] snapshots (see [`Workers::diff`])
+///
+/// All the lists are sorted by worker name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorkersDiff {
+    /// Workers not in the previous snapshot
+    pub added: Vec<String>,
+    /// Workers no longer reported
+    pub removed: Vec<String>,
+    /// Workers whose state changed: `(name, previous, current)`
+    pub changed: Vec<(String, WorkerState, WorkerState)>,
+}
+
+impl WorkersDiff {
+    /// Check if nothing changed
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl FromIterator<(String, Worker)> for Workers {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (String, Worker)>,
+    {
+        Self {
+            workers: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl IntoIterator for Workers {
+    type Item = (String, Worker);
+    type IntoIter = hash_map::IntoIter<String, Worker>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.workers.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Workers {
+    type Item = (&'a String, &'a Worker);
+    type IntoIter = hash_map::Iter<'a, String, Worker>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.workers.iter()
+    }
+}
+
+/// Workers borrowing their names and states from the input
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct WorkersRef<'a> {
+    /// Workers
+    #[serde(borrow, deserialize_with = "deserialize_borrowed_map")]
+    pub workers: HashMap<Cow<'a, str>, WorkerRef<'a>>,
+}
+
+impl From<WorkersRef<'_>> for Workers {
+    fn from(workers: WorkersRef<'_>) -> Self {
+        Self {
+            workers: workers
+                .workers
+                .into_iter()
+                .map(|(name, worker)| (name.into_owned(), worker.into()))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeSet, HashSet};
+
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::test_util::{self, BLOCK, POOL_STATS, USER_PROFILE, WORKERS, patched, workers};
+
+    #[test]
+    fn test_pool_stats_deserialization() {
+        let json = r#"{
+    "btc": {
+        "hash_rate_unit": "Gh/s",
+        "pool_active_workers": 1,
+        "pool_5m_hash_rate": 5727000000.746604,
+        "pool_60m_hash_rate": 5617000000.99422,
+        "pool_24h_hash_rate": 5517000000.88519,
+        "update_ts": 1699938300,
+        "blocks": {
+            "549753": {
+                "date_found": 1542002919,
+                "mining_duration": 3423,
+                "total_shares": 4640771710739,
+                "state": "confirmed",
+                "confirmations_left": 0,
+                "value": "12.92594863",
+                "user_reward": "0.00006194",
+                "pool_scoring_hash_rate": 5878745444.967269
+            }
+        },
+        "fpps_rate": 0.00000241
+    }
+}"#;
+        let user_profile: CurrencyResponse<PoolStats> = serde_json::from_str(json).unwrap();
+        assert_eq!(user_profile.data.pool_active_workers, 1);
+        assert_eq!(
+            user_profile.data,
+            PoolStats {
+                pool_active_workers: 1,
+                pool_5m_hash_rate: HashRate::new(HashRateUnit::GH, 5727000000.746604),
+                pool_60m_hash_rate: HashRate::new(HashRateUnit::GH, 5617000000.99422),
+                pool_24h_hash_rate: HashRate::new(HashRateUnit::GH, 5517000000.88519),
+                update_ts: 1699938300,
+                blocks: HashMap::from([(
+                    String::from("549753"),
+                    Block {
+                        height: Some(549753),
+                        date_found: 1542002919,
+                        mining_duration: 3423,
+                        total_shares: 4640771710739,
+                        state: BlockState::Confirmed,
+                        confirmations_left: 0,
+                        value: 12.92594863,
+                        user_reward: 0.00006194,
+                        pool_scoring_hash_rate: HashRate::new(HashRateUnit::GH, 5878745444.967269)
+                    }
+                )]),
+                fpps_rate: 0.00000241,
+                network_difficulty: None,
+                #[cfg(feature = "extra-fields")]
+                extra: HashMap::new(),
+            }
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "extra-fields")]
+    fn test_pool_stats_extra_fields() {
+        let json: Value = patched(POOL_STATS, json!({"pool_luck": 1.05}));
+        let stats: PoolStats = serde_json::from_value(json).unwrap();
+        assert_eq!(stats.extra.len(), 1);
+        assert_eq!(stats.extra["pool_luck"], 1.05);
+        assert_eq!(stats.update_ts, 1699938300);
+        assert_eq!(
+            stats.pool_5m_hash_rate,
+            HashRate::new(HashRateUnit::GH, 5727000000.746604)
+        );
+
+        // Round-trip
+        let value: Value = serde_json::to_value(&stats).unwrap();
+        assert_eq!(value["pool_luck"], 1.05);
+        let round_trip: PoolStats = serde_json::from_value(value).unwrap();
+        assert_eq!(round_trip, stats);
+    }
+
+    #[test]
+    fn test_pool_stats_serialization() {
+        let json: Value = patched(
+            POOL_STATS,
+            json!({
+                "pool_active_workers": 1,
+                "blocks": {"549753": serde_json::from_str::<Value>(BLOCK).unwrap()},
+            }),
+        );
+        let stats: PoolStats = serde_json::from_value(json).unwrap();
+
+        let value: serde_json::Value = serde_json::to_value(&stats).unwrap();
+        assert_eq!(value["hash_rate_unit"], "Gh/s");
+        assert_eq!(value["blocks"]["549753"]["state"], "confirmed");
+        assert_eq!(
+            value["blocks"]["549753"]["pool_scoring_hash_rate"],
+            5878745444.967269
+        );
+        assert!(value.get("network_difficulty").is_none());
+
+        let round_trip: PoolStats = serde_json::from_value(value).unwrap();
+        assert_eq!(round_trip, stats);
+    }
+
+    #[test]
+    fn test_user_profile_deserialization() {
+        let json = r#"{
+    "username": "username",
+    "btc": {
+        "all_time_reward": "0.15000000",
+        "hash_rate_unit": "Gh/s",
+        "hash_rate_5m": 27978,
+        "hash_rate_60m": 28191,
+        "hash_rate_24h": 28357,
+        "hash_rate_yesterday": 28197,
+        "low_workers": 0,
+        "off_workers": 0,
+        "ok_workers": 2,
+        "dis_workers": 2,
+        "current_balance": "0.15000000",
+        "today_reward": "0.000166667",
+        "estimated_reward": "0.00011940",
+        "shares_5m": 123,
+        "shares_60m": 1476,
+        "shares_24h": 35424,
+        "shares_yesterday": 0
+    }
+}"#;
+        let user_profile: CurrencyResponse<UserProfile> = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            user_profile.data,
+            UserProfile {
+                all_time_reward: 0.15,
+                hash_rate_5m: HashRate::new(HashRateUnit::GH, 27978.0),
+                hash_rate_60m: HashRate::new(HashRateUnit::GH, 28191.0),
+                hash_rate_24h: HashRate::new(HashRateUnit::GH, 28357.0),
+                hash_rate_yesterday: HashRate::new(HashRateUnit::GH, 28197.0),
+                low_workers: 0,
+                off_workers: 0,
+                ok_workers: 2,
+                dis_workers: 2,
+                current_balance: 0.15,
+                today_reward: 0.000166667,
+                estimated_reward: 0.00011940,
+                shares_5m: 123,
+                shares_60m: 1476,
+                shares_24h: 35424,
+                shares_yesterday: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_workers_deserialization() {
+        let json = r#"{
+    "btc": {
+        "workers": {
+            "username.worker1": {
+                "state": "ok",
+                "last_share": 1542103204,
+                "hash_rate_unit": "Gh/s",
+                "hash_rate_scoring": 15342,
+                "hash_rate_5m": 14977,
+                "hash_rate_60m": 15302,
+                "hash_rate_24h": 15351,
+                "shares_5m": 90304,
+                "shares_60m": 1125762,
+                "shares_24h": 20945364
+
+            },
+            "username.worker2": {
+                "state": "ok",
+                "last_share": 1542103200,
+                "hash_rate_unit": "Gh/s",
+                "hash_rate_scoring": 12952,
+                "hash_rate_5m": 13001,
+                "hash_rate_60m": 12889,
+                "hash_rate_24h": 13006,
+                "shares_5m": 90304,
+                "shares_60m": 1125762,
+                "shares_24h": 20945364
+            }
+        }
+    }
+}"#;
+        let user_profile: CurrencyResponse<Workers> = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            user_profile.data,
+            Workers {
+                workers: HashMap::from([
+                    (
+                        String::from("username.worker1"),
+                        Worker {
+                            state: WorkerState::Ok,
+                            last_share: 1542103204,
+                            hash_rate_scoring: HashRate::new(HashRateUnit::GH, 15342.0),
+                            hash_rate_5m: HashRate::new(HashRateUnit::GH, 14977.0),
+                            hash_rate_60m: HashRate::new(HashRateUnit::GH, 15302.0),
+                            hash_rate_24h: HashRate::new(HashRateUnit::GH, 15351.0),
+                            shares_5m: 90304,
+                            shares_60m: 1125762,
+                            shares_24h: 20945364
+                        }
+                    ),
+                    (
+                        String::from("username.worker2"),
+                        Worker {
+                            state: WorkerState::Ok,
+                            last_share: 1542103200,
+                            hash_rate_scoring: HashRate::new(HashRateUnit::GH, 12952.0),
+                            hash_rate_5m: HashRate::new(HashRateUnit::GH, 13001.0),
+                            hash_rate_60m: HashRate::new(HashRateUnit::GH, 12889.0),
+                            hash_rate_24h: HashRate::new(HashRateUnit::GH, 13006.0),
+                            shares_5m: 90304,
+                            shares_60m: 1125762,
+                            shares_24h: 20945364
+                        }
+                    )
+                ])
+            }
+        );
+    }
+
+    #[test]
+    fn test_confirmed_block_without_confirmations_left() {
+        let json = r#"{
+    "date_found": 1542002919,
+    "mining_duration": 3423,
+    "total_shares": 4640771710739,
+    "state": "confirmed",
+    "value": "12.92594863",
+    "user_reward": "0.00006194",
+    "pool_scoring_hash_rate": 5878745444.967269
+}"#;
+        let block: Block = serde_json::from_str(json).unwrap();
+        assert_eq!(block.height, None);
+        assert_eq!(block.state, BlockState::Confirmed);
+        assert_eq!(block.pool_scoring_hash_rate.unit(), HashRateUnit::H);
+        assert_eq!(block.pool_scoring_hash_rate.to_hashes(), 5878745444.967269);
+        assert_eq!(block.confirmations_left, 0);
+    }
+
+    fn block(date_found: u64, state: BlockState) -> Block {
+        Block {
+            height: None,
+            date_found,
+            mining_duration: 3423,
+            total_shares: 4640771710739,
+            state,
+            confirmations_left: 0,
+            value: 3.125,
+            user_reward: 0.00006194,
+            pool_scoring_hash_rate: HashRate::new(HashRateUnit::H, 5878745444.967269),
+        }
+    }
+
+    fn pool_stats(blocks: HashMap<String, Block>) -> PoolStats {
+        let mut stats: PoolStats = test_util::pool_stats();
+        stats.blocks = blocks;
+        stats
+    }
+
+    #[test]
+    fn test_block_state_deserialization() {
+        let states: Vec<BlockState> =
+            serde_json::from_str(r#"["confirmed", "new", "pending", "orphan", "orphaned", "foo"]"#)
+                .unwrap();
+        assert_eq!(
+            states,
+            vec![
+                BlockState::Confirmed,
+                BlockState::Pending,
+                BlockState::Pending,
+                BlockState::Orphaned,
+                BlockState::Orphaned,
+                BlockState::Unknown
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pool_stats_network_difficulty() {
+        let json: Value = patched(
+            POOL_STATS,
+            json!({
+                "hash_rate_unit": "Eh/s",
+                "pool_24h_hash_rate": 30,
+                "network_difficulty": 83148355189239.77,
+            }),
+        );
+        let stats: PoolStats = serde_json::from_value(json).unwrap();
+        assert_eq!(stats.network_difficulty, Some(83148355189239.77));
+        // Missing `pool_active_workers` defaults to zero
+        assert_eq!(stats.pool_active_workers, 0);
+        // ~3.3 hours
+        assert_eq!(stats.expected_block_interval().unwrap().as_secs() / 60, 198);
+
+        let stats = pool_stats(HashMap::new());
+        assert_eq!(stats.network_difficulty, None);
+        assert_eq!(stats.expected_block_interval(), None);
+    }
+
+    #[test]
+    fn test_pool_stats_fpps_rate() {
+        for (fpps_rate, expected) in [
+            (json!(0.00000241), 0.00000241),
+            (json!("0.00000241"), 0.00000241),
+            (json!(" 0.00000241 BTC/TH/day"), 0.00000241),
+            (json!(""), 0.0),
+        ] {
+            let json: Value = patched(POOL_STATS, json!({"fpps_rate": fpps_rate}));
+            let stats: PoolStats = serde_json::from_value(json).unwrap();
+            assert!((stats.fpps_rate - expected).abs() < 1e-15, "{fpps_rate}");
+        }
+
+        for fpps_rate in ["abc", "0.000241%"] {
+            let json: Value = patched(POOL_STATS, json!({"fpps_rate": fpps_rate}));
+            assert!(
+                serde_json::from_value::<PoolStats>(json).is_err(),
+                "{fpps_rate}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_pool_stats_blocks_by_state() {
+        let stats = pool_stats(HashMap::from([
+            (
+                String::from("549753"),
+                block(1542002919, BlockState::Confirmed),
+            ),
+            (
+                String::from("549760"),
+                block(1542006519, BlockState::Orphaned),
+            ),
+            (
+                String::from("549761"),
+                block(1542010119, BlockState::Pending),
+            ),
+            (
+                String::from("549762"),
+                block(1542013719, BlockState::Pending),
+            ),
+        ]));
+
+        let confirmed: Vec<&String> = stats.confirmed_blocks().map(|(h, _)| h).collect();
+        assert_eq!(confirmed, vec!["549753"]);
+
+        let orphaned: Vec<&String> = stats.orphaned_blocks().map(|(h, _)| h).collect();
+        assert_eq!(orphaned, vec!["549760"]);
+
+        let mut pending: Vec<&String> = stats.pending_blocks().map(|(h, _)| h).collect();
+        pending.sort();
+        assert_eq!(pending, vec!["549761", "549762"]);
+    }
+
+    #[test]
+    fn test_pool_stats_blocks_sorted_by_height() {
+        let stats = pool_stats(HashMap::from([
+            (
+                String::from("549762"),
+                block(1542013719, BlockState::Pending),
+            ),
+            (
+                String::from("549753"),
+                block(1542002919, BlockState::Confirmed),
+            ),
+            (
+                String::from("invalid"),
+                block(1542006519, BlockState::Unknown),
+            ),
+            (
+                String::from("549760"),
+                block(1542006519, BlockState::Orphaned),
+            ),
+        ]));
+
+        let heights: Vec<u64> = stats
+            .blocks_sorted_by_height()
+            .into_iter()
+            .map(|(h, _)| h)
+            .collect();
+        assert_eq!(heights, vec![549753, 549760, 549762]);
+    }
+
+    #[test]
+    fn test_hash_rate_convert() {
+        let hash_rate = HashRate::new(HashRateUnit::GH, 50000.0);
+        let converted = hash_rate.convert(HashRateUnit::TH);
+        assert_eq!(converted.unit(), HashRateUnit::TH);
+        assert_eq!(converted.value(), 50.0);
+        assert_eq!(converted.to_hashes(), hash_rate.to_hashes());
+    }
+
+    #[test]
+    fn test_hash_rate_to_hashes() {
+        assert_eq!(HashRate::new(HashRateUnit::H, 1.0).to_hashes(), 1.0);
+        assert_eq!(HashRate::new(HashRateUnit::KH, 1.0).to_hashes(), 1000.0);
+        assert_eq!(HashRate::new(HashRateUnit::TH, 2.0).to_hashes(), 2e12);
+    }
+
+    #[test]
+    fn test_hash_rate_scaled() {
+        let hash_rate = HashRate::new(HashRateUnit::GH, 5727000000.0).scaled();
+        assert_eq!(hash_rate.unit(), HashRateUnit::EH);
+        assert_eq!(format!("{hash_rate:.2}"), "5.73 Eh/s");
+
+        let hash_rate = HashRate::new(HashRateUnit::TH, 0.5).scaled();
+        assert_eq!(hash_rate.unit(), HashRateUnit::GH);
+        assert_eq!(hash_rate.to_string(), "500 Gh/s");
+
+        let zero = HashRate::new(HashRateUnit::TH, 0.0).scaled();
+        assert_eq!(zero.unit(), HashRateUnit::H);
+    }
+
+    #[test]
+    fn test_user_profile_reward_per_th_24h() {
+        let mut user_profile: UserProfile = test_util::user_profile();
+        user_profile.hash_rate_24h = HashRate::new(HashRateUnit::GH, 50000.0);
+        user_profile.today_reward = 0.0001;
+        assert!((user_profile.reward_per_th_24h() - 0.000002).abs() < 1e-12);
+
+        user_profile.hash_rate_24h = HashRate::new(HashRateUnit::GH, 0.0);
+        assert_eq!(user_profile.reward_per_th_24h(), 0.0);
+    }
+
+    #[test]
+    fn test_user_profile_realized_fpps() {
+        let mut user_profile: UserProfile = test_util::user_profile();
+        user_profile.hash_rate_24h = HashRate::new(HashRateUnit::GH, 100000.0);
+        user_profile.today_reward = 0.0001205;
+
+        // Half a day at 100 Th/s: 0.0001205 / (100 * 0.5) = 0.00000241
+        let fpps: f64 = user_profile.realized_fpps(43200);
+        assert!((fpps - 0.00000241).abs() < 1e-15);
+
+        assert_eq!(user_profile.realized_fpps(0), 0.0);
+
+        user_profile.hash_rate_24h = HashRate::new(HashRateUnit::GH, 0.0);
+        assert_eq!(user_profile.realized_fpps(43200), 0.0);
+    }
+
+    #[test]
+    fn test_user_profile_estimated_reward_progress() {
+        let mut user_profile: UserProfile = test_util::user_profile();
+        user_profile.today_reward = 0.0000597;
+        assert!((user_profile.estimated_reward_progress() - 0.5).abs() < 1e-12);
+        assert!((user_profile.remaining_estimated() - 0.0000597).abs() < 1e-12);
+
+        // Confirmed reward above the estimate
+        user_profile.today_reward = 0.000166667;
+        assert!(user_profile.estimated_reward_progress() > 1.0);
+        assert_eq!(user_profile.remaining_estimated(), 0.0);
+
+        user_profile.estimated_reward = 0.0;
+        assert_eq!(user_profile.estimated_reward_progress(), 0.0);
+    }
+
+    #[test]
+    fn test_user_profile_forecast_end_of_day_reward() {
+        let mut user_profile: UserProfile = test_util::user_profile();
+        user_profile.today_reward = 0.0000597;
+
+        // Half-elapsed day: the reward doubles
+        let forecast: f64 = user_profile.forecast_end_of_day_reward(43200);
+        assert!((forecast - 0.0001194).abs() < 1e-12);
+
+        // Clamped
+        assert_eq!(user_profile.forecast_end_of_day_reward(0), 0.0000597);
+        assert_eq!(user_profile.forecast_end_of_day_reward(86400), 0.0000597);
+        assert_eq!(user_profile.forecast_end_of_day_reward(100_000), 0.0000597);
+    }
+
+    #[test]
+    fn test_pool_stats_block_height() {
+        let block: Value = serde_json::from_str(BLOCK).unwrap();
+        let json: Value = patched(
+            POOL_STATS,
+            json!({"blocks": {"549753": block, "invalid": block}}),
+        );
+        let stats: PoolStats = serde_json::from_value(json).unwrap();
+        assert_eq!(stats.blocks["549753"].height, Some(549753));
+        assert_eq!(stats.blocks["invalid"].height, None);
+    }
+
+    #[test]
+    fn test_pool_stats_block_scoring_hash_rate_unit() {
+        let block: Value = serde_json::from_str(BLOCK).unwrap();
+        let json: Value = patched(POOL_STATS, json!({"blocks": {"549753": block}}));
+        let stats: PoolStats = serde_json::from_value(json).unwrap();
+        let hash_rate: HashRate = stats.blocks["549753"].pool_scoring_hash_rate;
+        assert_eq!(hash_rate.unit(), HashRateUnit::GH);
+        assert_eq!(hash_rate.value(), 5878745444.967269);
+        // Comparable with the pool hash rates
+        assert!(hash_rate > stats.pool_5m_hash_rate);
+    }
+
+    #[test]
+    fn test_hash_rate_sum() {
+        let total: HashRate = [
+            HashRate::new(HashRateUnit::TH, 1.0),
+            HashRate::new(HashRateUnit::GH, 500.0),
+        ]
+        .into_iter()
+        .sum();
+        assert_eq!(total, HashRate::new(HashRateUnit::TH, 1.5));
+
+        let empty: HashRate = std::iter::empty::<HashRate>().sum();
+        assert_eq!(empty, HashRate::new(HashRateUnit::H, 0.0));
+    }
+
+    #[test]
+    fn test_workers_average_hash_rate() {
+        let workers = workers();
+        assert_eq!(
+            workers.average_hash_rate_5m(),
+            HashRate::new(HashRateUnit::GH, 13989.0)
+        );
+        assert_eq!(
+            workers.average_hash_rate_60m(),
+            HashRate::new(HashRateUnit::GH, 14095.5)
+        );
+        assert_eq!(
+            workers.average_hash_rate_24h(),
+            HashRate::new(HashRateUnit::GH, 14178.5)
+        );
+
+        let empty = Workers {
+            workers: HashMap::new(),
+        };
+        assert_eq!(empty.average_hash_rate_5m().to_hashes(), 0.0);
+    }
+
+    #[test]
+    fn test_workers_ref_borrowed() {
+        let workers: WorkersRef = serde_json::from_str(WORKERS).unwrap();
+        assert_eq!(workers.workers.len(), 2);
+
+        // No allocation for names and states
+        for (name, worker) in workers.workers.iter() {
+            assert!(matches!(name, Cow::Borrowed(_)));
+            assert!(matches!(worker.state, Cow::Borrowed(_)));
+        }
+
+        let owned: Workers = workers.into();
+        assert_eq!(owned, self::workers());
+    }
+
+    #[test]
+    fn test_workers_ref_escaped() {
+        let json = r#"{
+    "workers": {
+        "username.worker\u0031": {
+            "state": "o\u006b",
+            "last_share": 1542103204,
+            "hash_rate_unit": "Gh/s",
+            "hash_rate_scoring": 15342,
+            "hash_rate_5m": 14977,
+            "hash_rate_60m": 15302,
+            "hash_rate_24h": 15351,
+            "shares_5m": 90304,
+            "shares_60m": 1125762,
+            "shares_24h": 20945364
+        }
+    }
+}"#;
+        let workers: WorkersRef = serde_json::from_str(json).unwrap();
+        let (name, worker) = workers.workers.iter().next().unwrap();
+        assert!(matches!(name, Cow::Owned(_)));
+        assert_eq!(name, "username.worker1");
+        assert!(matches!(worker.state, Cow::Owned(_)));
+        assert_eq!(worker.state, "ok");
+    }
+
+    #[test]
+    fn test_pool_stats_is_hashrate_dropping() {
+        let mut stats = pool_stats(HashMap::new());
+        stats.pool_60m_hash_rate = HashRate::new(HashRateUnit::PH, 100.0);
+        stats.pool_5m_hash_rate = HashRate::new(HashRateUnit::PH, 90.0);
+
+        // Exactly 10% drop
+        assert!(stats.is_hashrate_dropping(9.0));
+        assert!(stats.is_hashrate_dropping(9.99));
+        assert!(!stats.is_hashrate_dropping(10.0));
+        assert!(!stats.is_hashrate_dropping(11.0));
+
+        // Increasing hash rate
+        stats.pool_5m_hash_rate = HashRate::new(HashRateUnit::PH, 110.0);
+        assert!(!stats.is_hashrate_dropping(0.0));
+
+        // No hash rate
+        stats.pool_60m_hash_rate = HashRate::new(HashRateUnit::PH, 0.0);
+        assert!(!stats.is_hashrate_dropping(0.0));
+    }
+
+    fn worker(last_share: u64) -> Worker {
+        Worker {
+            state: WorkerState::Ok,
+            last_share,
+            hash_rate_scoring: HashRate::new(HashRateUnit::GH, 15342.0),
+            hash_rate_5m: HashRate::new(HashRateUnit::GH, 14977.0),
+            hash_rate_60m: HashRate::new(HashRateUnit::GH, 15302.0),
+            hash_rate_24h: HashRate::new(HashRateUnit::GH, 15351.0),
+            shares_5m: 90304,
+            shares_60m: 1125762,
+            shares_24h: 20945364,
+        }
+    }
+
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+
+    #[test]
+    fn test_worker_last_share_age() {
+        let worker = worker(now() - 600);
+        assert!(worker.last_share_age() >= Duration::from_secs(600));
+        assert!(worker.is_stale(Duration::from_secs(300)));
+        assert!(!worker.is_stale(Duration::from_secs(3600)));
+
+        // Last share in the future
+        let worker = self::worker(now() + 600);
+        assert_eq!(worker.last_share_age(), Duration::ZERO);
+        assert!(!worker.is_stale(Duration::ZERO));
+
+        let worker = self::worker(u64::MAX);
+        assert_eq!(worker.last_share_age(), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_pool_stats_blocks_since() {
+        let stats = pool_stats(HashMap::from([
+            (
+                String::from("549762"),
+                block(1542013719, BlockState::Pending),
+            ),
+            (
+                String::from("549753"),
+                block(1542002919, BlockState::Confirmed),
+            ),
+            (
+                String::from("549760"),
+                block(1542006519, BlockState::Confirmed),
+            ),
+        ]));
+
+        let dates: Vec<u64> = stats
+            .blocks_since(1542002919)
+            .into_iter()
+            .map(|b| b.date_found)
+            .collect();
+        assert_eq!(dates, vec![1542006519, 1542013719]);
+
+        assert_eq!(stats.blocks_since(0).len(), 3);
+        assert!(stats.blocks_since(1542013719).is_empty());
+    }
+
+    #[test]
+    fn test_currency_response_deserialization() {
+        let json: String = format!(r#"{{"username": "username", "btc": {USER_PROFILE}}}"#);
+        let res: CurrencyResponse<UserProfile> = serde_json::from_str(&json).unwrap();
+        assert_eq!(res.currency, Currency::Btc);
+        assert_eq!(res.data.ok_workers, 2);
+
+        let res: Result<CurrencyResponse<UserProfile>, _> =
+            serde_json::from_str(r#"{"username": "username"}"#);
+        assert!(res.is_err());
+
+        // Currency not enumerated by the crate
+        let ltc: Currency = Currency::from_str("ltc").unwrap();
+        let json = r#"{"username": "username", "ltc": 1, "btc": 2}"#;
+        assert!(serde_json::from_str::<CurrencyResponse<u64>>(r#"{"ltc": 1}"#).is_err());
+        let res: CurrencyResponse<u64> =
+            CurrencyResponse::deserialize_for(&mut serde_json::Deserializer::from_str(json), &ltc)
+                .unwrap();
+        assert_eq!(res.currency, ltc);
+        assert_eq!(res.data, 1);
+
+        let err = CurrencyResponse::<u64>::deserialize_for(
+            &mut serde_json::Deserializer::from_str(r#"{"btc": 2}"#),
+            &ltc,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("missing currency key `ltc`"));
+    }
+
+    #[test]
+    fn test_currency_mapping() {
+        let currencies: [(Currency, &str); 2] = [
+            (Currency::Btc, "btc"),
+            (Currency::from_str("ltc").unwrap(), "ltc"),
+        ];
+
+        for (currency, key) in currencies {
+            assert_eq!(currency.as_path_segment(), key);
+            assert_eq!(currency.response_key(), key);
+            assert_eq!(currency.to_string(), key);
+            assert_eq!(Currency::from_str(key).unwrap(), currency);
+        }
+    }
+
+    #[test]
+    fn test_currency_from_str() {
+        assert_eq!(Currency::from_str("BTC").unwrap(), Currency::Btc);
+        let ltc: Currency = Currency::from_str("Ltc").unwrap();
+        assert!(matches!(&ltc, Currency::Other(key) if key.as_str() == "ltc"));
+
+        for key in ["", "b tc", "btc/", "../btc", "../accounts", "a?b#c"] {
+            assert!(matches!(
+                Currency::from_str(key),
+                Err(Error::InvalidCurrency(..))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_workers_diff() {
+        let previous: Workers = workers();
+
+        // Unchanged
+        assert!(workers().diff(&previous).is_empty());
+
+        // Added
+        let mut current: Workers = workers();
+        let worker: Worker = current.workers["username.worker1"].clone();
+        current
+            .workers
+            .insert(String::from("username.worker3"), worker);
+        let diff: WorkersDiff = current.diff(&previous);
+        assert_eq!(diff.added, vec![String::from("username.worker3")]);
+        assert!(diff.removed.is_empty());
+        assert!(diff.changed.is_empty());
+
+        // Removed
+        let mut current: Workers = workers();
+        current.workers.remove("username.worker2");
+        let diff: WorkersDiff = current.diff(&previous);
+        assert!(diff.added.is_empty());
+        assert_eq!(diff.removed, vec![String::from("username.worker2")]);
+        assert!(diff.changed.is_empty());
+
+        // State changed
+        let mut current: Workers = workers();
+        current.workers.get_mut("username.worker1").unwrap().state = WorkerState::Off;
+        let diff: WorkersDiff = current.diff(&previous);
+        assert!(diff.added.is_empty());
+        assert!(diff.removed.is_empty());
+        assert_eq!(
+            diff.changed,
+            vec![(
+                String::from("username.worker1"),
+                WorkerState::Ok,
+                WorkerState::Off
+            )]
+        );
+
+        // Reversed snapshots
+        let diff: WorkersDiff = previous.diff(&current);
+        assert_eq!(
+            diff.changed,
+            vec![(
+                String::from("username.worker1"),
+                WorkerState::Off,
+                WorkerState::Ok
+            )]
+        );
+    }
+
+    #[test]
+    fn test_workers_from_iter() {
+        let workers: Workers = workers()
+            .workers
+            .into_iter()
+            .filter(|(_, w)| w.hash_rate_5m.value() > 14000.0)
+            .collect();
+        assert_eq!(workers.workers.len(), 1);
+        assert!(workers.workers.contains_key("username.worker1"));
+    }
+
+    #[test]
+    fn test_workers_iter_and_accessors() {
+        let workers: Workers = workers();
+        assert_eq!(workers.len(), workers.workers.len());
+        assert!(!workers.is_empty());
+        assert_eq!(
+            workers.get("username.worker1"),
+            workers.workers.get("username.worker1")
+        );
+        assert!(workers.get("username.unknown").is_none());
+
+        let mut names: Vec<&String> = Vec::new();
+        for (name, worker) in &workers {
+            assert_eq!(workers.get(name), Some(worker));
+            names.push(name);
+        }
+        assert_eq!(names.len(), workers.len());
+
+        let len: usize = workers.len();
+        let mut count: usize = 0;
+        for (name, worker) in workers.clone() {
+            assert_eq!(workers.get(&name), Some(&worker));
+            count += 1;
+        }
+        assert_eq!(count, len);
+
+        let empty: Workers = std::iter::empty().collect();
+        assert!(empty.is_empty());
+        assert_eq!(empty.len(), 0);
+        assert_eq!((&empty).into_iter().count(), 0);
+    }
+
+    #[test]
+    fn test_hashrate_point_deserialization() {
+        let json = r#"{
+    "timestamp": 1699938300,
+    "hash_rate_unit": "Gh/s",
+    "hash_rate": 5727000000.746604
+}"#;
+        let point: HashratePoint = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            point,
+            HashratePoint {
+                timestamp: 1699938300,
+                hash_rate: HashRate::new(HashRateUnit::GH, 5727000000.746604),
+            }
+        );
+    }
+
+    #[test]
+    fn test_worker_state_deserialization() {
+        let states: Vec<WorkerState> =
+            serde_json::from_str(r#"["ok", "low", "off", "dis", "foo"]"#).unwrap();
+        assert_eq!(
+            states,
+            vec![
+                WorkerState::Ok,
+                WorkerState::Low,
+                WorkerState::Off,
+                WorkerState::Disabled,
+                WorkerState::Unknown
+            ]
+        );
+    }
+
+    #[test]
+    fn test_worker_serialize_round_trip() {
+        let json = r#"{
+    "state": "low",
+    "last_share": 1542103204,
+    "hash_rate_unit": "Gh/s",
+    "hash_rate_scoring": 15342.5,
+    "hash_rate_5m": 14977.25,
+    "hash_rate_60m": 15302.5,
+    "hash_rate_24h": 15351.75,
+    "shares_5m": 90304,
+    "shares_60m": 1125762,
+    "shares_24h": 20945364
+}"#;
+        let mut worker: Worker = serde_json::from_str(json).unwrap();
+        let serialized: serde_json::Value = serde_json::to_value(&worker).unwrap();
+        let original: serde_json::Value = serde_json::from_str(json).unwrap();
+        assert_eq!(serialized, original);
+        assert_eq!(
+            serde_json::from_value::<Worker>(serialized).unwrap(),
+            worker
+        );
+
+        // Hash rates are converted to the scoring unit
+        worker.hash_rate_5m = worker.hash_rate_5m.convert(HashRateUnit::TH);
+        let serialized: serde_json::Value = serde_json::to_value(&worker).unwrap();
+        assert_eq!(serialized["hash_rate_unit"], "Gh/s");
+        assert!((serialized["hash_rate_5m"].as_f64().unwrap() - 14977.25).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_workers_total_shares() {
+        let mut workers = workers();
+        assert_eq!(workers.total_shares_5m(), 180608);
+        assert_eq!(workers.total_shares_60m(), 2251524);
+        assert_eq!(workers.total_shares_24h(), 41890728);
+
+        // Saturate instead of overflowing
+        for worker in workers.workers.values_mut() {
+            worker.shares_24h = u64::MAX;
+        }
+        assert_eq!(workers.total_shares_24h(), u64::MAX);
     }
 
     #[test]
-    fn test_user_profile_deserialization() {
-        let json = r#"{
-    "username": "username",
-    "btc": {
-        "all_time_reward": "0.15000000",
-        "hash_rate_unit": "Gh/s",
-        "hash_rate_5m": 27978,
-        "hash_rate_60m": 28191,
-        "hash_rate_24h": 28357,
-        "hash_rate_yesterday": 28197,
-        "low_workers": 0,
-        "off_workers": 0,
-        "ok_workers": 2,
-        "dis_workers": 2,
-        "current_balance": "0.15000000",
-        "today_reward": "0.000166667",
-        "estimated_reward": "0.00011940",
-        "shares_5m": 123,
-        "shares_60m": 1476,
-        "shares_24h": 35424,
-        "shares_yesterday": 0
+    fn test_workers_aggregations() {
+        let mut workers = workers();
+        assert_eq!(
+            workers.total_hash_rate_5m(),
+            HashRate::new(HashRateUnit::GH, 27978.0)
+        );
+        assert_eq!(
+            workers.total_hash_rate_60m(),
+            HashRate::new(HashRateUnit::GH, 28191.0)
+        );
+        assert_eq!(
+            workers.total_hash_rate_24h(),
+            HashRate::new(HashRateUnit::GH, 28357.0)
+        );
+        assert_eq!(
+            workers.count_by_state(),
+            HashMap::from([(WorkerState::Ok, 2)])
+        );
+        assert!(workers.offline().is_empty());
+
+        workers.workers.get_mut("username.worker2").unwrap().state = WorkerState::Off;
+        assert_eq!(
+            workers.count_by_state(),
+            HashMap::from([(WorkerState::Ok, 1), (WorkerState::Off, 1)])
+        );
+
+        let offline = workers.offline();
+        assert_eq!(offline.len(), 1);
+        assert_eq!(offline[0].0, "username.worker2");
+    }
+
+    fn daily_reward(date: u64, total_reward: f64, bos_plus_reward: f64) -> DailyReward {
+        DailyReward {
+            date,
+            total_reward,
+            mining_reward: total_reward - bos_plus_reward,
+            bos_plus_reward,
+            referral_bonus: 0.0,
+            referral_reward: 0.0,
+            calculation_date: date + 86400,
+        }
+    }
+
+    #[test]
+    fn test_daily_reward_bos_plus_fraction() {
+        assert_eq!(
+            daily_reward(1699920000, 0.002, 0.0005).bos_plus_fraction(),
+            0.25
+        );
+        assert_eq!(daily_reward(1699920000, 0.0, 0.0).bos_plus_fraction(), 0.0);
+
+        let rewards = DailyRewards {
+            daily_rewards: vec![
+                daily_reward(1699833600, 0.002, 0.0005),
+                daily_reward(1699920000, 0.002, 0.0),
+            ],
+        };
+        assert_eq!(rewards.bos_plus_fraction(), 0.125);
+
+        let empty = DailyRewards {
+            daily_rewards: Vec::new(),
+        };
+        assert_eq!(empty.bos_plus_fraction(), 0.0);
     }
+
+    #[test]
+    fn test_missing_optional_fields() {
+        let json = r#"{
+    "date": 1699920000,
+    "total_reward": "0.00110000"
 }"#;
-        let user_profile: BtcResponse<UserProfile> = serde_json::from_str(json).unwrap();
+        let reward: DailyReward = serde_json::from_str(json).unwrap();
+        assert_eq!(reward.total_reward, 0.0011);
+        assert_eq!(reward.mining_reward, 0.0);
+        assert_eq!(reward.referral_bonus, 0.0);
+        assert_eq!(reward.referral_reward, 0.0);
+        assert_eq!(reward.calculation_date, 0);
+
+        let json = r#"{
+    "hash_rate_unit": "Gh/s",
+    "hash_rate_5m": 0,
+    "hash_rate_60m": 0,
+    "hash_rate_24h": 0
+}"#;
+        let profile: UserProfile = serde_json::from_str(json).unwrap();
+        assert_eq!(profile.all_time_reward, 0.0);
+        assert_eq!(profile.ok_workers, 0);
+        assert_eq!(profile.shares_yesterday, 0);
+        assert!(profile.hash_rate_yesterday.is_zero());
+
+        let json = r#"{
+    "state": "off",
+    "last_share": 1542103204,
+    "hash_rate_unit": "Gh/s",
+    "hash_rate_scoring": 0,
+    "hash_rate_5m": 0,
+    "hash_rate_60m": 0,
+    "hash_rate_24h": 0
+}"#;
+        let worker: Worker = serde_json::from_str(json).unwrap();
+        assert_eq!(worker.shares_24h, 0);
+        let worker: WorkerRef = serde_json::from_str(json).unwrap();
+        assert_eq!(worker.shares_24h, 0);
+
+        let mut json: Value = serde_json::from_str(POOL_STATS).unwrap();
+        json.as_object_mut().unwrap().remove("blocks");
+        let stats: PoolStats = serde_json::from_value(json).unwrap();
+        assert!(stats.blocks.is_empty());
+
+        // Required fields are still required
+        assert!(serde_json::from_str::<DailyReward>(r#"{"date": 1699920000}"#).is_err());
+    }
+
+    #[test]
+    fn test_daily_rewards_pages() {
+        let rewards = DailyRewards {
+            daily_rewards: (0..5)
+                .map(|i| daily_reward(1699920000 + i * 86400, 0.001, 0.0))
+                .collect(),
+        };
+
+        let page = rewards.page(0, 2);
+        assert_eq!(page.daily_rewards.len(), 2);
+        assert_eq!(page.offset, 0);
+        assert!(page.has_more);
+
+        let page = rewards.page(4, 2);
+        assert_eq!(page.daily_rewards.len(), 1);
+        assert_eq!(page.daily_rewards[0].date, 1699920000 + 4 * 86400);
+        assert!(!page.has_more);
+
+        let page = rewards.page(10, 2);
+        assert!(page.daily_rewards.is_empty());
+        assert!(!page.has_more);
+
+        let pages: Vec<DailyRewardsPage> = rewards.pages(2).collect();
+        let offsets: Vec<u32> = pages.iter().map(|p| p.offset).collect();
+        assert_eq!(offsets, vec![0, 2, 4]);
+        let dates: Vec<u64> = pages
+            .into_iter()
+            .flat_map(|p| p.daily_rewards)
+            .map(|r| r.date)
+            .collect();
+        assert_eq!(dates.len(), 5);
+
+        assert_eq!(rewards.pages(0).count(), 0);
+        assert_eq!(rewards.pages(5).count(), 1);
+    }
+
+    #[test]
+    fn test_daily_rewards_merge() {
+        let a = DailyRewards {
+            daily_rewards: vec![
+                daily_reward(1699920000, 0.002, 0.0),
+                daily_reward(1699833600, 0.001, 0.0),
+            ],
+        };
+        let mut recalculated = daily_reward(1699920000, 0.003, 0.0);
+        recalculated.calculation_date += 3600;
+        let b = DailyRewards {
+            daily_rewards: vec![recalculated, daily_reward(1700006400, 0.004, 0.0)],
+        };
+
+        let merged = a.clone().merge(b.clone());
+        let dates: Vec<u64> = merged.daily_rewards.iter().map(|r| r.date).collect();
+        assert_eq!(dates, vec![1699833600, 1699920000, 1700006400]);
+        assert_eq!(merged.daily_rewards[1].total_reward, 0.003);
+
+        // The most recent calculation wins regardless of the order
+        let merged = b.merge(a);
+        assert_eq!(merged.daily_rewards.len(), 3);
+        assert_eq!(merged.daily_rewards[1].total_reward, 0.003);
+    }
+
+    #[test]
+    fn test_user_profile_static_summary() {
+        let mut profile: UserProfile = test_util::user_profile();
+        profile.low_workers = 1;
+        profile.dis_workers = 3;
+        profile.current_balance = 0.12;
         assert_eq!(
-            user_profile.btc,
-            UserProfile {
+            profile.static_summary(),
+            UserProfileSummary {
                 all_time_reward: 0.15,
-                hash_rate_5m: HashRate::new(HashRateUnit::GH, 27978.0),
-                hash_rate_60m: HashRate::new(HashRateUnit::GH, 28191.0),
-                hash_rate_24h: HashRate::new(HashRateUnit::GH, 28357.0),
-                hash_rate_yesterday: HashRate::new(HashRateUnit::GH, 28197.0),
-                low_workers: 0,
+                current_balance: 0.12,
+                low_workers: 1,
                 off_workers: 0,
                 ok_workers: 2,
-                dis_workers: 2,
-                current_balance: 0.15,
-                today_reward: 0.000166667,
-                estimated_reward: 0.00011940,
-                shares_5m: 123,
-                shares_60m: 1476,
-                shares_24h: 35424,
-                shares_yesterday: 0
+                dis_workers: 3,
             }
         );
+
+        assert!(UserProfile::is_volatile_field("hash_rate_5m"));
+        assert!(UserProfile::is_volatile_field("today_reward"));
+        assert!(!UserProfile::is_volatile_field("current_balance"));
+        assert!(!UserProfile::is_volatile_field("ok_workers"));
+        assert!(!UserProfile::is_volatile_field("unknown"));
     }
 
     #[test]
-    fn test_workers_deserialization() {
+    fn test_daily_reward_empty_string() {
         let json = r#"{
-    "btc": {
-        "workers": {
-            "username.worker1": {
-                "state": "ok",
-                "last_share": 1542103204,
-                "hash_rate_unit": "Gh/s",
-                "hash_rate_scoring": 15342,
-                "hash_rate_5m": 14977,
-                "hash_rate_60m": 15302,
-                "hash_rate_24h": 15351,
-                "shares_5m": 90304,
-                "shares_60m": 1125762,
-                "shares_24h": 20945364
+    "date": 1699920000,
+    "total_reward": "0.00110000",
+    "mining_reward": "0.00100000",
+    "bos_plus_reward": "0.00010000",
+    "referral_bonus": "",
+    "referral_reward": "0.00000000",
+    "calculation_date": 1700006400
+}"#;
+        let reward: DailyReward = serde_json::from_str(json).unwrap();
+        assert_eq!(reward.referral_bonus, 0.0);
+        assert_eq!(reward.total_reward, 0.0011);
+    }
 
-            },
-            "username.worker2": {
-                "state": "ok",
-                "last_share": 1542103200,
-                "hash_rate_unit": "Gh/s",
-                "hash_rate_scoring": 12952,
-                "hash_rate_5m": 13001,
-                "hash_rate_60m": 12889,
-                "hash_rate_24h": 13006,
-                "shares_5m": 90304,
-                "shares_60m": 1125762,
-                "shares_24h": 20945364
-            }
+    #[test]
+    fn test_daily_reward_scientific_notation() {
+        let json = r#"{
+    "date": 1699920000,
+    "total_reward": "1.67e-4",
+    "mining_reward": "1.5E-4",
+    "bos_plus_reward": " 1.7e-5 ",
+    "referral_bonus": "0e0",
+    "referral_reward": 2.5e-9,
+    "calculation_date": 1700006400
+}"#;
+        let reward: DailyReward = serde_json::from_str(json).unwrap();
+        assert_eq!(reward.total_reward, 0.000167);
+        assert_eq!(reward.mining_reward, 0.00015);
+        assert_eq!(reward.bos_plus_reward, 0.000017);
+        assert_eq!(reward.referral_bonus, 0.0);
+        assert_eq!(reward.referral_reward, 0.0000000025);
+    }
+
+    #[test]
+    fn test_hash_rate_unit_map_key() {
+        let map: HashMap<HashRateUnit, f64> =
+            HashMap::from([(HashRateUnit::GH, 27978.0), (HashRateUnit::TH, 28.357)]);
+
+        let json: String = serde_json::to_string(&map).unwrap();
+        assert!(json.contains(r#""Gh/s":27978.0"#));
+        assert!(json.contains(r#""Th/s":28.357"#));
+
+        let deserialized: HashMap<HashRateUnit, f64> = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, map);
+
+        // Aliases
+        let deserialized: HashMap<HashRateUnit, f64> =
+            serde_json::from_str(r#"{"GH/s": 1.0}"#).unwrap();
+        assert_eq!(deserialized, HashMap::from([(HashRateUnit::GH, 1.0)]));
+    }
+
+    #[test]
+    fn test_block_luck() {
+        // 2^32 H/s, so a difficulty of 3600 takes 3600 sec on average
+        let mut block = block(1542002919, BlockState::Confirmed);
+        block.pool_scoring_hash_rate = HashRate::new(HashRateUnit::H, 4294967296.0);
+
+        block.mining_duration = 3600;
+        assert_eq!(block.luck(3600.0), 100.0);
+
+        // Found in half the expected time
+        block.mining_duration = 1800;
+        assert_eq!(block.luck(3600.0), 200.0);
+
+        // Took twice as long
+        block.mining_duration = 7200;
+        assert_eq!(block.luck(3600.0), 50.0);
+
+        // Same, in Gh/s
+        block.pool_scoring_hash_rate = HashRate::new(HashRateUnit::GH, 4.294967296);
+        assert!((block.luck(3600.0) - 50.0).abs() < 1e-9);
+
+        block.mining_duration = 0;
+        assert_eq!(block.luck(3600.0), 0.0);
+    }
+
+    #[test]
+    fn test_block_confirmations() {
+        let confirmed = block(1542002919, BlockState::Confirmed);
+        assert!(confirmed.is_confirmed());
+        assert_eq!(confirmed.confirmations_remaining(), None);
+
+        let mut pending = block(1542013719, BlockState::Pending);
+        pending.confirmations_left = 50;
+        assert!(!pending.is_confirmed());
+        assert_eq!(pending.confirmations_remaining(), Some(50));
+
+        // Inconsistent: confirmed state, with confirmations left
+        let mut inconsistent = block(1542013719, BlockState::Confirmed);
+        inconsistent.confirmations_left = 1;
+        assert!(!inconsistent.is_confirmed());
+        assert_eq!(inconsistent.confirmations_remaining(), Some(1));
+
+        let orphaned = block(1542013719, BlockState::Orphaned);
+        assert!(!orphaned.is_confirmed());
+        assert_eq!(orphaned.confirmations_remaining(), None);
+    }
+
+    #[test]
+    fn test_block_estimated_confirmation_time() {
+        let stats = pool_stats(HashMap::from([
+            (
+                String::from("549753"),
+                block(1542002919, BlockState::Confirmed),
+            ),
+            (
+                String::from("549760"),
+                block(1542006519, BlockState::Confirmed),
+            ),
+            (
+                String::from("549761"),
+                block(1542013719, BlockState::Pending),
+            ),
+        ]));
+
+        // (1542013719 - 1542002919) / 2
+        let interval: Duration = stats.average_block_interval().unwrap();
+        assert_eq!(interval, Duration::from_secs(5400));
+
+        let mut pending = block(1542013719, BlockState::Pending);
+        pending.confirmations_left = 50;
+        assert_eq!(
+            pending.estimated_confirmation_time(interval),
+            Duration::from_secs(270000)
+        );
+
+        let confirmed = block(1542002919, BlockState::Confirmed);
+        assert_eq!(
+            confirmed.estimated_confirmation_time(interval),
+            Duration::ZERO
+        );
+
+        let single = pool_stats(HashMap::from([(
+            String::from("549753"),
+            block(1542002919, BlockState::Confirmed),
+        )]));
+        assert_eq!(single.average_block_interval(), None);
+    }
+
+    #[test]
+    fn test_pool_stats_recent_blocks() {
+        let now: u64 = now();
+        let stats = pool_stats(HashMap::from([
+            (
+                String::from("549753"),
+                block(now - 3 * 3600, BlockState::Confirmed),
+            ),
+            (
+                String::from("549754"),
+                block(now - 3600, BlockState::Confirmed),
+            ),
+            (String::from("549755"), block(now - 60, BlockState::Pending)),
+            // Clock skew
+            (String::from("549756"), block(now + 30, BlockState::Pending)),
+        ]));
+
+        let recent = stats.recent_blocks(Duration::from_secs(2 * 3600));
+        let heights: Vec<u64> = recent.iter().map(|(height, _)| *height).collect();
+        assert_eq!(heights, vec![549756, 549755, 549754]);
+
+        assert_eq!(stats.recent_blocks(Duration::from_secs(4 * 3600)).len(), 4);
+        assert_eq!(stats.recent_blocks(Duration::ZERO).len(), 1);
+    }
+
+    #[test]
+    fn test_pool_stats_round_stats() {
+        let mut blocks: HashMap<String, Block> = HashMap::new();
+        for (height, mining_duration) in [("549753", 600), ("549754", 3423), ("549755", 1200)] {
+            let mut block = block(1542002919, BlockState::Confirmed);
+            block.mining_duration = mining_duration;
+            blocks.insert(String::from(height), block);
+        }
+        let stats = pool_stats(blocks);
+
+        assert_eq!(
+            stats.round_stats(),
+            Some(RoundStats {
+                count: 3,
+                avg_duration: Duration::from_secs(1741),
+                min_duration: Duration::from_secs(600),
+                max_duration: Duration::from_secs(3423),
+            })
+        );
+
+        assert_eq!(pool_stats(HashMap::new()).round_stats(), None);
+    }
+
+    #[test]
+    fn test_pool_stats_block_window() {
+        let stats = pool_stats(HashMap::from([
+            (
+                String::from("549753"),
+                block(1542002919, BlockState::Confirmed),
+            ),
+            (
+                String::from("549755"),
+                block(1542013719, BlockState::Pending),
+            ),
+            (
+                String::from("549754"),
+                block(1542008319, BlockState::Confirmed),
+            ),
+        ]));
+        assert_eq!(stats.block_window(), Some((1542002919, 1542013719)));
+
+        let empty = pool_stats(HashMap::new());
+        assert_eq!(empty.block_window(), None);
+    }
+
+    #[test]
+    fn test_hash_rate_number_or_string() {
+        for value in ["12.5", r#""12.5""#] {
+            let json = format!(
+                r#"{{
+    "state": "ok",
+    "last_share": 1542103204,
+    "hash_rate_unit": "Th/s",
+    "hash_rate_scoring": {value},
+    "hash_rate_5m": {value},
+    "hash_rate_60m": {value},
+    "hash_rate_24h": {value},
+    "shares_5m": 90304,
+    "shares_60m": 1125762,
+    "shares_24h": 20945364
+}}"#
+            );
+            let worker: Worker = serde_json::from_str(&json).unwrap();
+            assert_eq!(worker.hash_rate_5m, HashRate::new(HashRateUnit::TH, 12.5));
+
+            let json = format!(
+                r#"{{
+    "date_found": 1542002919,
+    "mining_duration": 3423,
+    "total_shares": 4640771710739,
+    "state": "confirmed",
+    "value": {value},
+    "user_reward": "0.00006194",
+    "pool_scoring_hash_rate": {value}
+}}"#
+            );
+            let block: Block = serde_json::from_str(&json).unwrap();
+            assert_eq!(block.value, 12.5);
+            assert_eq!(block.pool_scoring_hash_rate.to_hashes(), 12.5);
         }
     }
-}"#;
-        let user_profile: BtcResponse<Workers> = serde_json::from_str(json).unwrap();
+
+    #[test]
+    fn test_hash_rate_ord() {
+        let mut hash_rates = vec![
+            HashRate::new(HashRateUnit::TH, 1.0),
+            HashRate::new(HashRateUnit::GH, 500.0),
+            HashRate::new(HashRateUnit::PH, 0.002),
+            HashRate::new(HashRateUnit::MH, 0.0),
+        ];
+        hash_rates.sort();
         assert_eq!(
-            user_profile.btc,
-            Workers {
-                workers: HashMap::from([
-                    (
-                        String::from("username.worker1"),
-                        Worker {
-                            state: String::from("ok"),
-                            last_share: 1542103204,
-                            hash_rate_scoring: HashRate::new(HashRateUnit::GH, 15342.0),
-                            hash_rate_5m: HashRate::new(HashRateUnit::GH, 14977.0),
-                            hash_rate_60m: HashRate::new(HashRateUnit::GH, 15302.0),
-                            hash_rate_24h: HashRate::new(HashRateUnit::GH, 15351.0),
-                            shares_5m: 90304,
-                            shares_60m: 1125762,
-                            shares_24h: 20945364
-                        }
-                    ),
-                    (
-                        String::from("username.worker2"),
-                        Worker {
-                            state: String::from("ok"),
-                            last_share: 1542103200,
-                            hash_rate_scoring: HashRate::new(HashRateUnit::GH, 12952.0),
-                            hash_rate_5m: HashRate::new(HashRateUnit::GH, 13001.0),
-                            hash_rate_60m: HashRate::new(HashRateUnit::GH, 12889.0),
-                            hash_rate_24h: HashRate::new(HashRateUnit::GH, 13006.0),
-                            shares_5m: 90304,
-                            shares_60m: 1125762,
-                            shares_24h: 20945364
-                        }
-                    )
-                ])
-            }
+            hash_rates,
+            vec![
+                HashRate::new(HashRateUnit::MH, 0.0),
+                HashRate::new(HashRateUnit::GH, 500.0),
+                HashRate::new(HashRateUnit::TH, 1.0),
+                HashRate::new(HashRateUnit::PH, 0.002),
+            ]
+        );
+
+        // Same hashrate in different units
+        assert_eq!(
+            HashRate::new(HashRateUnit::GH, 1000.0),
+            HashRate::new(HashRateUnit::TH, 1.0)
+        );
+
+        let set: BTreeSet<HashRate> = BTreeSet::from([
+            HashRate::new(HashRateUnit::GH, 1000.0),
+            HashRate::new(HashRateUnit::TH, 1.0),
+            HashRate::new(HashRateUnit::TH, 2.0),
+        ]);
+        assert_eq!(set.len(), 2);
+
+        let set: HashSet<HashRate> = HashSet::from([
+            HashRate::new(HashRateUnit::GH, 1000.0),
+            HashRate::new(HashRateUnit::TH, 1.0),
+        ]);
+        assert_eq!(set.len(), 1);
+
+        // NaN is ordered after all the other values
+        let nan = HashRate::new(HashRateUnit::TH, f64::NAN);
+        assert!(nan > HashRate::new(HashRateUnit::TH, f64::INFINITY));
+        assert_eq!(nan, nan);
+    }
+
+    #[test]
+    fn test_hash_rate_try_to_hashes() {
+        let hash_rate = HashRate::new(HashRateUnit::TH, 100.0);
+        assert_eq!(hash_rate.try_to_hashes(), Some(100e12));
+
+        // Finite value, overflowing once converted
+        let hash_rate = HashRate::new(HashRateUnit::YH, 1e290);
+        assert!(hash_rate.value().is_finite());
+        assert_eq!(hash_rate.to_hashes(), f64::INFINITY);
+        assert_eq!(hash_rate.try_to_hashes(), None);
+
+        assert_eq!(
+            HashRate::new(HashRateUnit::H, f64::NAN).try_to_hashes(),
+            None
+        );
+    }
+
+    #[test]
+    #[allow(clippy::field_reassign_with_default)]
+    fn test_default_construction() {
+        // Non-exhaustive models are built from their default, like in downstream tests
+        let mut worker = Worker::default();
+        worker.state = WorkerState::Ok;
+        worker.hash_rate_5m = HashRate::new(HashRateUnit::TH, 100.0);
+        assert_eq!(Worker::default().state, WorkerState::Unknown);
+
+        let mut workers = Workers::default();
+        assert!(workers.is_empty());
+        workers
+            .workers
+            .insert(String::from("username.worker1"), worker);
+        assert_eq!(
+            workers.total_hash_rate_5m(),
+            HashRate::new(HashRateUnit::TH, 100.0)
+        );
+
+        let mut stats = PoolStats::default();
+        stats.update_ts = 1699938300;
+        assert!(stats.blocks.is_empty());
+        assert_eq!(stats.pool_5m_hash_rate, HashRate::default());
+        assert_eq!(HashRate::default().unit(), HashRateUnit::H);
+        assert_eq!(Block::default().state, BlockState::Unknown);
+    }
+
+    #[test]
+    fn test_hash_rate_approx_eq() {
+        let gh = HashRate::new(HashRateUnit::GH, 15342.0);
+        let th = HashRate::new(HashRateUnit::TH, 15.342);
+        assert!(gh.approx_eq(&th, 0.0));
+        assert!(th.approx_eq(&gh, 1.0));
+
+        // Rounding error of the unit conversion
+        let gh = HashRate::new(HashRateUnit::GH, 0.13);
+        let th = gh.convert(HashRateUnit::TH);
+        assert_ne!(gh, th);
+        assert!(gh.approx_eq(&th, 1e-3));
+        assert!(!gh.approx_eq(&th, 0.0));
+
+        let other = HashRate::new(HashRateUnit::TH, 15.343);
+        assert!(!HashRate::new(HashRateUnit::GH, 15342.0).approx_eq(&other, 1e6));
+        assert!(HashRate::new(HashRateUnit::GH, 15342.0).approx_eq(&other, 2e9));
+    }
+
+    #[test]
+    fn test_hash_rate_is_zero() {
+        assert!(HashRate::new(HashRateUnit::GH, 0.0).is_zero());
+        assert!(HashRate::new(HashRateUnit::TH, -0.0).is_zero());
+        assert!(!HashRate::new(HashRateUnit::H, f64::MIN_POSITIVE).is_zero());
+        assert!(!HashRate::new(HashRateUnit::GH, 1e-12).is_zero());
+        assert!(!HashRate::new(HashRateUnit::GH, 14977.0).is_zero());
+    }
+
+    #[test]
+    fn test_hash_rate_nice_ceiling() {
+        for (hash_rate, expected) in [
+            (HashRate::new(HashRateUnit::TH, 1.0), 1.0),
+            (HashRate::new(HashRateUnit::TH, 1.2), 2.0),
+            (HashRate::new(HashRateUnit::TH, 2.0), 2.0),
+            (HashRate::new(HashRateUnit::TH, 3.0), 5.0),
+            (HashRate::new(HashRateUnit::TH, 7.0), 10.0),
+            (HashRate::new(HashRateUnit::TH, 0.45), 0.5),
+            (HashRate::new(HashRateUnit::GH, 27978.0), 50000.0),
+            (HashRate::new(HashRateUnit::GH, 5727000000.746604), 1e10),
+        ] {
+            let nice: HashRate = hash_rate.nice_ceiling();
+            assert_eq!(nice.unit(), hash_rate.unit());
+            assert!(
+                (nice.value() - expected).abs() < expected * 1e-12,
+                "{hash_rate:?} -> {nice:?}"
+            );
+        }
+
+        let zero = HashRate::new(HashRateUnit::GH, 0.0);
+        assert_eq!(zero.nice_ceiling().value(), 0.0);
+    }
+
+    #[test]
+    fn test_hash_rate_delta() {
+        let before = HashRate::new(HashRateUnit::TH, 3.0);
+        let after = HashRate::new(HashRateUnit::GH, 1800.0);
+
+        let delta = after.delta(&before);
+        assert!(delta.is_negative());
+        assert_eq!(delta.to_hashes(), -1.2e12);
+        assert_eq!(delta.to_string(), "-1.2 Th/s");
+
+        let delta = before.delta(&after);
+        assert!(!delta.is_negative());
+        assert_eq!(delta.to_string(), "1.2 Th/s");
+        assert_eq!(format!("{delta:.3}"), "1.200 Th/s");
+
+        assert_eq!(
+            HashRate::new(HashRateUnit::GH, 500.0)
+                .delta(&HashRate::new(HashRateUnit::TH, 1.0))
+                .to_string(),
+            "-500.0 Gh/s"
+        );
+        assert_eq!(before.delta(&before).to_string(), "0.0 H/s");
+    }
+
+    #[test]
+    #[cfg(feature = "time")]
+    fn test_offsetdatetime_accessors() {
+        use time::macros::datetime;
+
+        let reward = daily_reward(1699920000, 0.002, 0.0);
+        assert_eq!(
+            reward.date_offsetdatetime(),
+            Some(datetime!(2023-11-14 00:00 UTC))
+        );
+        assert_eq!(
+            reward.calculation_date_offsetdatetime(),
+            Some(datetime!(2023-11-15 00:00 UTC))
+        );
+
+        let stats = pool_stats(HashMap::new());
+        assert_eq!(
+            stats.update_ts_offsetdatetime(),
+            Some(datetime!(2023-11-14 05:05 UTC))
         );
     }
 }
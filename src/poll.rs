@@ -0,0 +1,199 @@
+//! Adaptive polling
+//!
+//! The pool stats are refreshed on a fixed cadence (i.e. every 5 minutes), reported by [`PoolStats::update_ts`]:
+//! [`AdaptivePoll`] learns it from successive updates, to poll shortly after the next one
+//! (see [`BraiinsPoolClient::pool_stats_adaptive_stream`](crate::client::BraiinsPoolClient::pool_stats_adaptive_stream)).
+
+use std::future::Future;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use futures_util::stream::{self, Stream};
+
+use crate::error::Error;
+use crate::model::PoolStats;
+
+const DEFAULT_MARGIN: Duration = Duration::from_secs(5);
+
+/// Adaptive poll scheduler
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdaptivePoll {
+    default_interval: Duration,
+    margin: Duration,
+    last_update_ts: Option<u64>,
+    cadence: Option<u64>,
+}
+
+impl AdaptivePoll {
+    /// Construct a new scheduler, polling every `default_interval` until the cadence is learned
+    pub fn new(default_interval: Duration) -> Self {
+        Self {
+            default_interval,
+            margin: DEFAULT_MARGIN,
+            last_update_ts: None,
+            cadence: None,
+        }
+    }
+
+    /// Set how long after the expected update to poll (default: 5 sec)
+    #[inline]
+    pub fn margin(mut self, margin: Duration) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// Record polled stats
+    ///
+    /// Stats not newer than the latest recorded update are ignored.
+    pub fn record(&mut self, stats: &PoolStats) {
+        self.record_update_ts(stats.update_ts);
+    }
+
+    /// Record the update timestamp of polled stats
+    pub fn record_update_ts(&mut self, update_ts: u64) {
+        match self.last_update_ts {
+            Some(last) if update_ts <= last => {}
+            Some(last) => {
+                self.cadence = Some(update_ts - last);
+                self.last_update_ts = Some(update_ts);
+            }
+            None => self.last_update_ts = Some(update_ts),
+        }
+    }
+
+    /// Get the learned update cadence
+    #[inline]
+    pub fn cadence(&self) -> Option<Duration> {
+        self.cadence.map(Duration::from_secs)
+    }
+
+    /// Get the delay until the next poll
+    #[inline]
+    pub fn next_poll_delay(&self) -> Duration {
+        self.next_poll_delay_at(unix_now())
+    }
+
+    /// Get the delay until the next poll, at `now` (unix time)
+    ///
+    /// Fires `margin` after the expected next update (`last update_ts + cadence`).
+    /// If the expected update is overdue, waits `margin`;
+    /// if the cadence isn't learned yet, waits `default_interval`.
+    pub fn next_poll_delay_at(&self, now: u64) -> Duration {
+        match (self.last_update_ts, self.cadence) {
+            (Some(last), Some(cadence)) => {
+                let expected: u64 = last.saturating_add(cadence);
+                Duration::from_secs(expected.saturating_sub(now)) + self.margin
+            }
+            _ => self.default_interval,
+        }
+    }
+}
+
+/// Get the current unix time
+pub(crate) fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Stream the stats returned by `fetch`, polled right away and then as scheduled by `poll`
+///
+/// `now` is the clock (unix time) the delays are computed against.
+pub(crate) fn adaptive_stream<F, Fut, C>(
+    poll: AdaptivePoll,
+    fetch: F,
+    now: C,
+) -> impl Stream<Item = Result<PoolStats, Error>>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<PoolStats, Error>>,
+    C: Fn() -> u64,
+{
+    stream::unfold(
+        (poll, fetch, now, true),
+        |(mut poll, mut fetch, now, first)| async move {
+            if !first {
+                tokio::time::sleep(poll.next_poll_delay_at(now())).await;
+            }
+
+            let result: Result<PoolStats, Error> = fetch().await;
+            if let Ok(stats) = &result {
+                poll.record(stats);
+            }
+
+            Some((result, (poll, fetch, now, false)))
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::pin::pin;
+
+    use futures_util::StreamExt;
+    use tokio::time::Instant;
+
+    use super::*;
+
+    #[test]
+    fn test_adaptive_poll() {
+        let mut poll = AdaptivePoll::new(Duration::from_secs(60)).margin(Duration::from_secs(2));
+        assert_eq!(poll.next_poll_delay_at(1699938000), Duration::from_secs(60));
+
+        poll.record_update_ts(1699938000);
+        assert_eq!(poll.cadence(), None);
+        assert_eq!(poll.next_poll_delay_at(1699938003), Duration::from_secs(60));
+
+        // Two updates 300 sec apart, polled 3 sec after the latest one
+        poll.record_update_ts(1699938300);
+        assert_eq!(poll.cadence(), Some(Duration::from_secs(300)));
+        assert_eq!(
+            poll.next_poll_delay_at(1699938303),
+            Duration::from_secs(299)
+        );
+
+        // Same stats polled again: ignored
+        poll.record_update_ts(1699938300);
+        assert_eq!(poll.cadence(), Some(Duration::from_secs(300)));
+
+        // Overdue update
+        assert_eq!(poll.next_poll_delay_at(1699938700), Duration::from_secs(2));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_adaptive_stream() {
+        let base: u64 = 1699938000;
+        let start: Instant = Instant::now();
+        let now = move || base + start.elapsed().as_secs();
+
+        // Two updates 300 sec apart
+        let mut updates = [base - 300, base].into_iter();
+        let fetch = move || {
+            let update_ts: u64 = updates.next().unwrap_or(base + 300);
+            async move {
+                Ok(PoolStats {
+                    update_ts,
+                    ..Default::default()
+                })
+            }
+        };
+
+        let poll = AdaptivePoll::new(Duration::from_secs(1)).margin(Duration::from_secs(5));
+        let mut stream = pin!(adaptive_stream(poll, fetch, now));
+
+        // Polled right away
+        let stats = stream.next().await.unwrap().unwrap();
+        assert_eq!(stats.update_ts, base - 300);
+        assert_eq!(start.elapsed(), Duration::ZERO);
+
+        // Cadence not learned yet: default interval
+        let stats = stream.next().await.unwrap().unwrap();
+        assert_eq!(stats.update_ts, base);
+        assert_eq!(start.elapsed(), Duration::from_secs(1));
+
+        // Next update expected at `base + 300`: polled 5 sec after it
+        let stats = stream.next().await.unwrap().unwrap();
+        assert_eq!(stats.update_ts, base + 300);
+        assert_eq!(start.elapsed(), Duration::from_secs(305));
+    }
+}
@@ -1,4 +1,20 @@
 //! Prelude
+//!
+//! Glob re-export of the public surface, including the enums:
+//!
+//! ```rust
+//! use braiinspool::prelude::*;
+//!
+//! let _: Currency = Currency::Btc;
+//! let _: BlockState = BlockState::Confirmed;
+//! let _: WorkerState = WorkerState::Ok;
+//! let _: HashRateUnit = HashRateUnit::TH;
+//! let _: ErrorKind = ErrorKind::NotFound;
+//!
+//! // No collision with `url::*`
+//! let _: Url = Url::parse("https://pool.braiins.com").unwrap();
+//! let _: ParseError = ParseError::EmptyHost;
+//! ```
 
 #![allow(unknown_lints)]
 #![allow(ambiguous_glob_reexports)]
@@ -6,7 +22,20 @@
 
 pub use url::*;
 
+pub use crate::api::{self, *};
+pub use crate::api_key::{self, *};
 pub use crate::builder::{self, *};
+pub use crate::calc::{self, *};
 pub use crate::client::{self, *};
+pub use crate::dashboard::{self, *};
 pub use crate::error::{self, *};
+pub use crate::export::{self, *};
+#[cfg(feature = "fixtures")]
+pub use crate::fixtures::{self, *};
+pub use crate::history::{self, *};
+pub use crate::lenient::{self, *};
+pub use crate::meta::{self, *};
 pub use crate::model::{self, *};
+pub use crate::poll::{self, *};
+pub use crate::rewards::{self, *};
+pub use crate::stats::{self, *};
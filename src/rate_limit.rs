@@ -0,0 +1,104 @@
+//! Client-side rate limiting
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tokio::time::{self, Instant};
+
+/// Token bucket
+///
+/// Holds up to `capacity` tokens, refilled continuously at `capacity / per`.
+/// Callers reserve a token and wait for it when the bucket is empty,
+/// so concurrent requests are served in order of arrival.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    capacity: f64,
+    /// Tokens per second
+    rate: f64,
+    state: Mutex<State>,
+}
+
+#[derive(Debug)]
+struct State {
+    /// Available tokens (negative when some are already reserved)
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    /// Returns `None` if `requests` or `per` is zero
+    pub(crate) fn new(requests: u32, per: Duration) -> Option<Self> {
+        if requests == 0 || per.is_zero() {
+            return None;
+        }
+
+        let capacity: f64 = requests as f64;
+
+        Some(Self {
+            capacity,
+            rate: capacity / per.as_secs_f64(),
+            state: Mutex::new(State {
+                tokens: capacity,
+                last_refill: Instant::now(),
+            }),
+        })
+    }
+
+    /// Reserve a token, returning how long to wait before using it
+    fn reserve(&self) -> Duration {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+
+        // Refill
+        let now: Instant = Instant::now();
+        let elapsed: f64 = now.duration_since(state.last_refill).as_secs_f64();
+        state.tokens = (state.tokens + elapsed * self.rate).min(self.capacity);
+        state.last_refill = now;
+
+        state.tokens -= 1.0;
+
+        if state.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-state.tokens / self.rate)
+        }
+    }
+
+    /// Wait until a token is available
+    ///
+    /// The token stays consumed if the future is dropped while waiting.
+    pub(crate) async fn acquire(&self) {
+        let wait: Duration = self.reserve();
+
+        if !wait.is_zero() {
+            time::sleep(wait).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limiter_disabled() {
+        assert!(RateLimiter::new(0, Duration::from_secs(1)).is_none());
+        assert!(RateLimiter::new(1, Duration::ZERO).is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_rate_limiter_spacing() {
+        let limiter = RateLimiter::new(2, Duration::from_secs(1)).unwrap();
+        let start: Instant = Instant::now();
+
+        // Burst up to the capacity
+        limiter.acquire().await;
+        limiter.acquire().await;
+        assert_eq!(start.elapsed(), Duration::ZERO);
+
+        // Then one every 500 ms
+        limiter.acquire().await;
+        assert_eq!(start.elapsed(), Duration::from_millis(500));
+        limiter.acquire().await;
+        assert_eq!(start.elapsed(), Duration::from_secs(1));
+    }
+}
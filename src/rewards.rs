@@ -0,0 +1,98 @@
+//! Reward analytics
+//!
+//! Iterator adapters over [`DailyReward`]s, composing with the standard iterators:
+//!
+//! ```rust
+//! use braiinspool::prelude::*;
+//!
+//! fn total_since(rewards: &DailyRewards, from: u64) -> f64 {
+//!     rewards
+//!         .daily_rewards
+//!         .iter()
+//!         .in_date_range(from, u64::MAX)
+//!         .total_reward_sum()
+//! }
+//! ```
+
+use crate::model::DailyReward;
+
+/// Extension trait for iterators over [`DailyReward`]s
+pub trait RewardIteratorExt<'a>: Iterator<Item = &'a DailyReward> + Sized {
+    /// Sum of the [`total_reward`](DailyReward::total_reward)s
+    #[inline]
+    fn total_reward_sum(self) -> f64 {
+        self.map(|reward| reward.total_reward).sum()
+    }
+
+    /// Reward with the highest [`total_reward`](DailyReward::total_reward)
+    ///
+    /// Returns the last one on ties and `None` if the iterator is empty.
+    #[inline]
+    fn max_by_reward(self) -> Option<&'a DailyReward> {
+        self.max_by(|a, b| a.total_reward.total_cmp(&b.total_reward))
+    }
+
+    /// Keep the rewards with a [`date`](DailyReward::date) in `from..=to` (unix time)
+    #[inline]
+    fn in_date_range(self, from: u64, to: u64) -> impl Iterator<Item = &'a DailyReward> {
+        self.filter(move |reward| reward.date >= from && reward.date <= to)
+    }
+}
+
+impl<'a, I> RewardIteratorExt<'a> for I where I: Iterator<Item = &'a DailyReward> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reward(date: u64, total_reward: f64) -> DailyReward {
+        DailyReward {
+            date,
+            total_reward,
+            mining_reward: total_reward,
+            bos_plus_reward: 0.0,
+            referral_bonus: 0.0,
+            referral_reward: 0.0,
+            calculation_date: date + 86400,
+        }
+    }
+
+    #[test]
+    fn test_reward_iterator_ext() {
+        let rewards: Vec<DailyReward> = vec![
+            reward(1699833600, 0.0001),
+            reward(1699920000, 0.0003),
+            reward(1700006400, 0.0002),
+        ];
+
+        assert!((rewards.iter().total_reward_sum() - 0.0006).abs() < 1e-12);
+        assert_eq!(rewards.iter().max_by_reward(), Some(&rewards[1]));
+
+        let in_range: Vec<&DailyReward> = rewards
+            .iter()
+            .in_date_range(1699920000, 1700006400)
+            .collect();
+        assert_eq!(in_range, vec![&rewards[1], &rewards[2]]);
+
+        // Compose
+        let total: f64 = rewards
+            .iter()
+            .in_date_range(1699833600, 1699920000)
+            .total_reward_sum();
+        assert!((total - 0.0004).abs() < 1e-12);
+        assert_eq!(
+            rewards
+                .iter()
+                .in_date_range(1700006400, u64::MAX)
+                .max_by_reward(),
+            Some(&rewards[2])
+        );
+
+        // Empty
+        assert_eq!(
+            rewards.iter().in_date_range(0, 1000).total_reward_sum(),
+            0.0
+        );
+        assert_eq!(rewards.iter().in_date_range(0, 1000).max_by_reward(), None);
+    }
+}
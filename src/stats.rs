@@ -0,0 +1,92 @@
+//! Client stats
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::error::{Error, ErrorKind};
+
+/// Client stats
+///
+/// Snapshot of the requests made by a [`BraiinsPoolClient`](crate::client::BraiinsPoolClient) and its clones.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClientStats {
+    /// Number of requests
+    pub requests: u64,
+    /// Number of successful requests
+    pub successes: u64,
+    /// Number of failed requests, by error kind
+    ///
+    /// Kinds that never occurred are not included.
+    pub errors_by_kind: HashMap<ErrorKind, u64>,
+}
+
+impl ClientStats {
+    /// Total number of failed requests
+    #[inline]
+    pub fn errors(&self) -> u64 {
+        self.errors_by_kind.values().sum()
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Counters {
+    requests: AtomicU64,
+    successes: AtomicU64,
+    errors: [AtomicU64; ErrorKind::ALL.len()],
+}
+
+impl Counters {
+    pub(crate) fn record<T>(&self, result: &Result<T, Error>) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+
+        match result {
+            Ok(..) => self.successes.fetch_add(1, Ordering::Relaxed),
+            Err(e) => self.errors[e.kind() as usize].fetch_add(1, Ordering::Relaxed),
+        };
+    }
+
+    pub(crate) fn snapshot(&self) -> ClientStats {
+        ClientStats {
+            requests: self.requests.load(Ordering::Relaxed),
+            successes: self.successes.load(Ordering::Relaxed),
+            errors_by_kind: ErrorKind::ALL
+                .into_iter()
+                .zip(self.errors.iter())
+                .map(|(kind, count)| (kind, count.load(Ordering::Relaxed)))
+                .filter(|(_, count)| *count > 0)
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_kind_index() {
+        for (index, kind) in ErrorKind::ALL.into_iter().enumerate() {
+            assert_eq!(kind as usize, index);
+        }
+    }
+
+    #[test]
+    fn test_counters() {
+        let counters = Counters::default();
+        counters.record(&Ok::<(), Error>(()));
+        counters.record(&Err::<(), Error>(Error::NotFound));
+        counters.record(&Err::<(), Error>(Error::NotFound));
+        counters.record(&Err::<(), Error>(Error::TooManyRequests));
+
+        let stats = counters.snapshot();
+        assert_eq!(stats.requests, 4);
+        assert_eq!(stats.successes, 1);
+        assert_eq!(stats.errors(), 3);
+        assert_eq!(stats.errors_by_kind.get(&ErrorKind::NotFound), Some(&2));
+        assert_eq!(
+            stats.errors_by_kind.get(&ErrorKind::TooManyRequests),
+            Some(&1)
+        );
+        assert!(!stats.errors_by_kind.contains_key(&ErrorKind::ServerError));
+    }
+}
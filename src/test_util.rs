@@ -0,0 +1,104 @@
+//! Test fixtures
+//!
+//! Responses data, without the currency key (see [`btc`]).
+
+use serde_json::Value;
+
+use crate::model::{PoolStats, UserProfile, Workers};
+
+pub(crate) const POOL_STATS: &str = r#"{
+    "hash_rate_unit": "Gh/s",
+    "pool_5m_hash_rate": 5727000000.746604,
+    "pool_60m_hash_rate": 5617000000.99422,
+    "pool_24h_hash_rate": 5517000000.88519,
+    "update_ts": 1699938300,
+    "blocks": {},
+    "fpps_rate": 0.00000241
+}"#;
+
+/// Block of [`POOL_STATS`] (`549753`)
+pub(crate) const BLOCK: &str = r#"{
+    "date_found": 1542002919,
+    "mining_duration": 3423,
+    "total_shares": 4640771710739,
+    "state": "confirmed",
+    "confirmations_left": 0,
+    "value": "12.92594863",
+    "user_reward": "0.00006194",
+    "pool_scoring_hash_rate": 5878745444.967269
+}"#;
+
+pub(crate) const USER_PROFILE: &str = r#"{
+    "all_time_reward": "0.15000000",
+    "hash_rate_unit": "Gh/s",
+    "hash_rate_5m": 27978,
+    "hash_rate_60m": 28191,
+    "hash_rate_24h": 28357,
+    "hash_rate_yesterday": 28197,
+    "low_workers": 0,
+    "off_workers": 0,
+    "ok_workers": 2,
+    "dis_workers": 2,
+    "current_balance": "0.15000000",
+    "today_reward": "0.000166667",
+    "estimated_reward": "0.00011940",
+    "shares_5m": 123,
+    "shares_60m": 1476,
+    "shares_24h": 35424,
+    "shares_yesterday": 0
+}"#;
+
+pub(crate) const WORKERS: &str = r#"{
+    "workers": {
+        "username.worker1": {
+            "state": "ok",
+            "last_share": 1542103204,
+            "hash_rate_unit": "Gh/s",
+            "hash_rate_scoring": 15342,
+            "hash_rate_5m": 14977,
+            "hash_rate_60m": 15302,
+            "hash_rate_24h": 15351,
+            "shares_5m": 90304,
+            "shares_60m": 1125762,
+            "shares_24h": 20945364
+        },
+        "username.worker2": {
+            "state": "ok",
+            "last_share": 1542103200,
+            "hash_rate_unit": "Gh/s",
+            "hash_rate_scoring": 12952,
+            "hash_rate_5m": 13001,
+            "hash_rate_60m": 12889,
+            "hash_rate_24h": 13006,
+            "shares_5m": 90304,
+            "shares_60m": 1125762,
+            "shares_24h": 20945364
+        }
+    }
+}"#;
+
+pub(crate) fn pool_stats() -> PoolStats {
+    serde_json::from_str(POOL_STATS).unwrap()
+}
+
+pub(crate) fn user_profile() -> UserProfile {
+    serde_json::from_str(USER_PROFILE).unwrap()
+}
+
+pub(crate) fn workers() -> Workers {
+    serde_json::from_str(WORKERS).unwrap()
+}
+
+/// Parse a fixture, overriding some of its top-level fields
+pub(crate) fn patched(fixture: &str, patch: Value) -> Value {
+    let mut value: Value = serde_json::from_str(fixture).unwrap();
+    if let (Value::Object(map), Value::Object(patch)) = (&mut value, patch) {
+        map.extend(patch);
+    }
+    value
+}
+
+/// Wrap a fixture with the currency key, like the API responses (i.e. `{"btc": ...}`)
+pub(crate) fn btc(fixture: &str) -> String {
+    format!(r#"{{"btc": {fixture}}}"#)
+}
@@ -1,12 +1,45 @@
-use std::fmt::Display;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+use std::marker::PhantomData;
 use std::str::FromStr;
 
+use serde::de::{MapAccess, Visitor};
 use serde::{Deserialize, Deserializer};
+#[cfg(feature = "time")]
+use time::OffsetDateTime;
 
+/// Deserialize a number from either a JSON number or a string
+///
+/// Empty (or whitespace-only) strings are treated as zero (i.e. rewards of fresh accounts).
+/// Strings are parsed with [`FromStr`], so floats accept scientific notation (i.e. `1.67e-4`).
 pub(crate) fn deserialize_number_from_string<'de, T, D>(deserializer: D) -> Result<T, D::Error>
 where
     D: Deserializer<'de>,
-    T: FromStr + serde::Deserialize<'de>,
+    T: FromStr + Default + serde::Deserialize<'de>,
+    <T as FromStr>::Err: Display,
+{
+    deserialize_number(deserializer, str::trim)
+}
+
+/// Like [`deserialize_number_from_string`], but ignoring a trailing unit in strings
+/// (i.e. `0.00000241 BTC/TH/day`)
+pub(crate) fn deserialize_number_with_unit<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr + Default + serde::Deserialize<'de>,
+    <T as FromStr>::Err: Display,
+{
+    deserialize_number(deserializer, |s| {
+        s.split_whitespace().next().unwrap_or_default()
+    })
+}
+
+/// Deserialize a number from either a JSON number or a string, extracting the number from the string with `number`
+fn deserialize_number<'de, T, D>(deserializer: D, number: fn(&str) -> &str) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr + Default + serde::Deserialize<'de>,
     <T as FromStr>::Err: Display,
 {
     #[derive(Deserialize)]
@@ -17,7 +50,266 @@ where
     }
 
     match StringOrInt::<T>::deserialize(deserializer)? {
-        StringOrInt::String(s) => s.parse::<T>().map_err(serde::de::Error::custom),
+        StringOrInt::String(s) => {
+            let s: &str = number(&s);
+
+            if s.is_empty() {
+                return Ok(T::default());
+            }
+
+            s.parse::<T>().map_err(serde::de::Error::custom)
+        }
         StringOrInt::Number(i) => Ok(i),
     }
 }
+
+/// Convert a unix timestamp to UTC [`OffsetDateTime`]
+#[cfg(feature = "time")]
+pub(crate) fn offset_date_time(timestamp: u64) -> Option<OffsetDateTime> {
+    let timestamp: i64 = i64::try_from(timestamp).ok()?;
+    OffsetDateTime::from_unix_timestamp(timestamp).ok()
+}
+
+/// Check if the JSON nesting depth (objects and arrays) exceeds `max_depth`
+///
+/// Doesn't validate the JSON: malformed input is left to the deserializer.
+pub(crate) fn json_depth_exceeds(json: &[u8], max_depth: usize) -> bool {
+    let mut depth: usize = 0;
+    let mut in_string: bool = false;
+    let mut escaped: bool = false;
+
+    for byte in json.iter() {
+        if in_string {
+            match byte {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match byte {
+            b'"' => in_string = true,
+            b'{' | b'[' => {
+                depth += 1;
+                if depth > max_depth {
+                    return true;
+                }
+            }
+            b'}' | b']' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+
+    false
+}
+
+/// Get an excerpt of `json` around the position (1-based line and column) of a deserialization error
+///
+/// Returns `None` if the position is unknown (i.e. for data errors not tied to the input).
+pub(crate) fn json_error_snippet(json: &[u8], line: usize, column: usize) -> Option<String> {
+    const RADIUS: usize = 24;
+
+    if line == 0 {
+        return None;
+    }
+
+    let line_start: usize = if line == 1 {
+        0
+    } else {
+        json.iter()
+            .enumerate()
+            .filter(|(_, b)| **b == b'\n')
+            .nth(line - 2)
+            .map(|(i, _)| i + 1)?
+    };
+    let offset: usize = (line_start + column.saturating_sub(1)).min(json.len());
+
+    let start: usize = offset.saturating_sub(RADIUS);
+    let end: usize = (offset + RADIUS).min(json.len());
+    let snippet = String::from_utf8_lossy(&json[start..end]);
+    let snippet: String = snippet.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    if snippet.is_empty() {
+        return None;
+    }
+
+    Some(snippet)
+}
+
+/// String borrowed from the input when possible (i.e. no escape sequences)
+struct BorrowedStr<'de>(Cow<'de, str>);
+
+impl<'de> Deserialize<'de> for BorrowedStr<'de> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct StrVisitor;
+
+        impl<'de> Visitor<'de> for StrVisitor {
+            type Value = Cow<'de, str>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a string")
+            }
+
+            fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Ok(Cow::Borrowed(v))
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Ok(Cow::Owned(v.to_owned()))
+            }
+
+            fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Ok(Cow::Owned(v))
+            }
+        }
+
+        deserializer.deserialize_str(StrVisitor).map(Self)
+    }
+}
+
+/// Deserialize a map borrowing its keys from the input when possible
+pub(crate) fn deserialize_borrowed_map<'de, D, V>(
+    deserializer: D,
+) -> Result<HashMap<Cow<'de, str>, V>, D::Error>
+where
+    D: Deserializer<'de>,
+    V: Deserialize<'de>,
+{
+    struct MapVisitor<V>(PhantomData<V>);
+
+    impl<'de, V> Visitor<'de> for MapVisitor<V>
+    where
+        V: Deserialize<'de>,
+    {
+        type Value = HashMap<Cow<'de, str>, V>;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a map")
+        }
+
+        fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+        where
+            A: MapAccess<'de>,
+        {
+            let mut values = HashMap::with_capacity(map.size_hint().unwrap_or_default());
+
+            while let Some(BorrowedStr(key)) = map.next_key()? {
+                let value: V = map.next_value()?;
+                values.insert(key, value);
+            }
+
+            Ok(values)
+        }
+    }
+
+    deserializer.deserialize_map(MapVisitor(PhantomData))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Deserialize)]
+    struct Value {
+        #[serde(deserialize_with = "deserialize_number_from_string")]
+        value: f64,
+    }
+
+    #[test]
+    fn test_deserialize_number_from_string() {
+        let value: Value = serde_json::from_str(r#"{"value": "0.15"}"#).unwrap();
+        assert_eq!(value.value, 0.15);
+
+        let value: Value = serde_json::from_str(r#"{"value": 0.15}"#).unwrap();
+        assert_eq!(value.value, 0.15);
+
+        let value: Value = serde_json::from_str(r#"{"value": 15}"#).unwrap();
+        assert_eq!(value.value, 15.0);
+
+        let value: Value = serde_json::from_str(r#"{"value": " 0.15 "}"#).unwrap();
+        assert_eq!(value.value, 0.15);
+
+        assert!(serde_json::from_str::<Value>(r#"{"value": "abc"}"#).is_err());
+        assert!(serde_json::from_str::<Value>(r#"{"value": true}"#).is_err());
+    }
+
+    #[test]
+    fn test_deserialize_number_from_empty_string() {
+        let value: Value = serde_json::from_str(r#"{"value": ""}"#).unwrap();
+        assert_eq!(value.value, 0.0);
+
+        let value: Value = serde_json::from_str(r#"{"value": "  "}"#).unwrap();
+        assert_eq!(value.value, 0.0);
+    }
+
+    #[test]
+    fn test_deserialize_number_with_unit() {
+        #[derive(Deserialize)]
+        struct Rate {
+            #[serde(deserialize_with = "deserialize_number_with_unit")]
+            value: f64,
+        }
+
+        let rate: Rate = serde_json::from_str(r#"{"value": " 0.00000241 BTC/TH/day"}"#).unwrap();
+        assert_eq!(rate.value, 0.00000241);
+
+        let rate: Rate = serde_json::from_str(r#"{"value": 0.00000241}"#).unwrap();
+        assert_eq!(rate.value, 0.00000241);
+
+        let rate: Rate = serde_json::from_str(r#"{"value": ""}"#).unwrap();
+        assert_eq!(rate.value, 0.0);
+
+        // Not a unit
+        assert!(serde_json::from_str::<Rate>(r#"{"value": "0.000241%"}"#).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "time")]
+    fn test_offset_date_time() {
+        assert_eq!(offset_date_time(0), Some(OffsetDateTime::UNIX_EPOCH));
+        assert_eq!(
+            offset_date_time(1699920000).map(|dt| dt.unix_timestamp()),
+            Some(1699920000)
+        );
+        assert_eq!(offset_date_time(u64::MAX), None);
+    }
+
+    #[test]
+    fn test_json_depth_exceeds() {
+        let json = br#"{"a": [{"b": 1}], "c": "[[[{{{"}"#;
+        assert!(!json_depth_exceeds(json, 3));
+        assert!(json_depth_exceeds(json, 2));
+
+        // Escaped quotes don't end the string
+        let json = br#"{"a": "\"[[[["}"#;
+        assert!(!json_depth_exceeds(json, 1));
+
+        assert!(!json_depth_exceeds(b"1", 0));
+        assert!(json_depth_exceeds(b"[]", 0));
+    }
+
+    #[test]
+    fn test_json_error_snippet() {
+        let json = b"{\n  \"a\": 1,\n  \"fpps_rate\": oops\n}";
+        let err = serde_json::from_slice::<serde_json::Value>(json).unwrap_err();
+        let snippet = json_error_snippet(json, err.line(), err.column()).unwrap();
+        assert!(snippet.contains("\"fpps_rate\": oops"));
+
+        assert_eq!(json_error_snippet(json, 0, 0), None);
+        assert_eq!(json_error_snippet(json, 10, 1), None);
+    }
+}
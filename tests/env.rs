@@ -0,0 +1,21 @@
+use std::env::{self, VarError};
+
+use braiinspool::prelude::*;
+
+// Single test, since the environment is shared by the whole process
+#[test]
+fn test_builder_from_env() {
+    // SAFETY: no other thread reads or writes the environment in this test binary
+    unsafe { env::remove_var(API_KEY_ENV_VAR) };
+    let res = BraiinsPoolClientBuilder::from_env();
+    assert!(matches!(res, Err(Error::Env(VarError::NotPresent))));
+
+    unsafe { env::set_var(API_KEY_ENV_VAR, "") };
+    let res = BraiinsPoolClientBuilder::from_env();
+    assert!(matches!(res, Err(Error::InvalidApiKey)));
+
+    unsafe { env::set_var(API_KEY_ENV_VAR, "apikey") };
+    let builder = BraiinsPoolClientBuilder::from_env().unwrap();
+    assert_eq!(builder.api_key.as_ref().map(ApiKey::as_str), Some("apikey"));
+    assert!(builder.build().is_ok());
+}